@@ -0,0 +1,379 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+//! On-disk cache of LLM responses, keyed by model and normalized prompt.
+
+use crate::api::model_client::ModelClient;
+use crate::models::types::{CellContext, DimensionalPosition, Plan, RealTimeContext, Thought};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total > 0 {
+            self.hits as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    model: String,
+    created_at: DateTime<Utc>,
+    response: String,
+}
+
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> std::io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the cached response if one exists and has not outlived the TTL.
+    /// Every call counts as either a hit or a miss.
+    pub fn get(&self, model: &str, prompt: &str) -> Option<String> {
+        let path = self.entry_path(model, prompt);
+        let entry = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<CacheEntry>(&json).ok())
+            .filter(|entry| entry.model == model);
+
+        match entry {
+            Some(entry) if !self.is_expired(&entry) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.response)
+            }
+            Some(_) => {
+                let _ = fs::remove_file(&path);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, model: &str, prompt: &str, response: &str) {
+        // Empty responses are failures, not answers worth replaying
+        if response.trim().is_empty() {
+            return;
+        }
+
+        let entry = CacheEntry {
+            model: model.to_string(),
+            created_at: Utc::now(),
+            response: response.to_string(),
+        };
+
+        let result = serde_json::to_string(&entry)
+            .map_err(std::io::Error::from)
+            .and_then(|json| fs::write(self.entry_path(model, prompt), json));
+        if let Err(e) = result {
+            eprintln!("Error writing response cache entry: {}", e);
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        let age = Utc::now().signed_duration_since(entry.created_at);
+        age.to_std().is_ok_and(|age| age > self.ttl)
+    }
+
+    fn entry_path(&self, model: &str, prompt: &str) -> PathBuf {
+        let key = fnv1a_64(format!("{}\n{}", model, normalize_prompt(prompt)).as_bytes());
+        self.dir.join(format!("{:016x}.json", key))
+    }
+}
+
+/// Collapses whitespace and case so cosmetic prompt differences share an entry.
+fn normalize_prompt(prompt: &str) -> String {
+    prompt
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// FNV-1a keeps cache file names stable across builds, unlike `DefaultHasher`
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Wraps any `ModelClient` so its answers are replayed from a `ResponseCache`.
+/// Each trait call is keyed by its name and arguments; calls a client makes to
+/// itself inside those methods go straight to the model. Real-time context is
+/// never cached, and a replayed plan gets fresh ids.
+pub struct CachedClient {
+    inner: Box<dyn ModelClient>,
+    model: String,
+    cache: ResponseCache,
+}
+
+impl CachedClient {
+    pub fn new(inner: Box<dyn ModelClient>, model: &str, cache: ResponseCache) -> Self {
+        Self {
+            inner,
+            model: model.to_string(),
+            cache,
+        }
+    }
+
+    async fn cached<T, F>(&self, request: String, call: F) -> Result<T, Box<dyn Error>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T, Box<dyn Error>>>,
+    {
+        if let Some(hit) = self.cache.get(&self.model, &request).and_then(|json| serde_json::from_str(&json).ok()) {
+            return Ok(hit);
+        }
+        let result = call.await?;
+        self.cache.put(&self.model, &request, &serde_json::to_string(&result)?);
+        Ok(result)
+    }
+}
+
+fn request_key(method: &str, arguments: impl Serialize) -> Result<String, Box<dyn Error>> {
+    Ok(format!("{}\n{}", method, serde_json::to_string(&arguments)?))
+}
+
+// A replayed plan is a new proposal, so it must not share ids with the original
+fn with_fresh_ids(mut plan: Plan) -> Plan {
+    let ids: HashMap<Uuid, Uuid> = plan.nodes.iter().map(|node| (node.id, Uuid::new_v4())).collect();
+    for node in &mut plan.nodes {
+        node.id = ids[&node.id];
+        for dependency in &mut node.dependencies {
+            *dependency = ids.get(dependency).copied().unwrap_or(*dependency);
+        }
+    }
+    plan.id = Uuid::new_v4();
+    plan.created_at = Utc::now();
+    plan
+}
+
+#[async_trait]
+impl ModelClient for CachedClient {
+    async fn generate_contextual_thought(
+        &self,
+        cell_context: &CellContext,
+        real_time_context: &RealTimeContext,
+        colony_mission: &str,
+    ) -> Result<(String, f64, Vec<String>), Box<dyn Error>> {
+        let request = request_key("generate_contextual_thought", (cell_context, real_time_context, colony_mission))?;
+        self.cached(request, self.inner.generate_contextual_thought(cell_context, real_time_context, colony_mission)).await
+    }
+
+    async fn create_plan(&self, thoughts: &[Thought]) -> Result<Plan, Box<dyn Error>> {
+        let request = request_key("create_plan", thoughts)?;
+        let mut plan = self.cached(request, self.inner.create_plan(thoughts)).await.map(with_fresh_ids)?;
+        plan.thoughts = thoughts.to_vec();
+        Ok(plan)
+    }
+
+    async fn evaluate_dimensional_state(
+        &self,
+        position: &DimensionalPosition,
+        thoughts: &[Thought],
+        plans: &[Plan],
+    ) -> Result<(f64, f64), Box<dyn Error>> {
+        let request = request_key("evaluate_dimensional_state", (position, thoughts, plans))?;
+        self.cached(request, self.inner.evaluate_dimensional_state(position, thoughts, plans)).await
+    }
+
+    async fn compress_memories(&self, memories: &[String]) -> Result<String, Box<dyn Error>> {
+        let request = request_key("compress_memories", memories)?;
+        self.cached(request, self.inner.compress_memories(memories)).await
+    }
+
+    async fn gather_real_time_context(
+        &self,
+        cell_thoughts: Option<Vec<String>>,
+    ) -> Result<RealTimeContext, Box<dyn Error>> {
+        self.inner.gather_real_time_context(cell_thoughts).await
+    }
+
+    async fn generate_contextual_thoughts_batch(
+        &self,
+        cell_contexts: &[(Uuid, &CellContext)],
+        real_time_context: &RealTimeContext,
+        colony_mission: &str,
+        recent_thoughts: &[Thought],
+    ) -> Result<HashMap<Uuid, Vec<(String, f64, Vec<String>)>>, Box<dyn Error>> {
+        let request = request_key("generate_contextual_thoughts_batch", (cell_contexts, real_time_context, colony_mission, recent_thoughts))?;
+        self.cached(request, self.inner.generate_contextual_thoughts_batch(cell_contexts, real_time_context, colony_mission, recent_thoughts)).await
+    }
+
+    async fn query_llm(&self, prompt: &str) -> Result<String, Box<dyn Error>> {
+        if let Some(cached) = self.cache.get(&self.model, prompt) {
+            return Ok(cached);
+        }
+        let response = self.inner.query_llm(prompt).await?;
+        self.cache.put(&self.model, prompt, &response);
+        Ok(response)
+    }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        Some(self.cache.stats())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(ttl: Duration) -> (ResponseCache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("response_cache_{}", Uuid::new_v4()));
+        (ResponseCache::new(&dir, ttl).unwrap(), dir)
+    }
+
+    #[test]
+    fn test_miss_then_hit_is_counted() {
+        let (cache, dir) = temp_cache(Duration::from_secs(60));
+        assert_eq!(cache.get("model", "prompt"), None);
+        cache.put("model", "prompt", "answer");
+        assert_eq!(cache.get("model", "prompt").as_deref(), Some("answer"));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_prompts_differing_in_whitespace_and_case_share_an_entry() {
+        let (cache, dir) = temp_cache(Duration::from_secs(60));
+        cache.put("model", "Plan the  NEXT step\n", "answer");
+        assert_eq!(cache.get("model", "  plan the next\tstep").as_deref(), Some("answer"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_models_do_not_share_entries() {
+        let (cache, dir) = temp_cache(Duration::from_secs(60));
+        cache.put("first", "prompt", "from first");
+        cache.put("second", "prompt", "from second");
+        assert_eq!(cache.get("first", "prompt").as_deref(), Some("from first"));
+        assert_eq!(cache.get("second", "prompt").as_deref(), Some("from second"));
+        assert_eq!(cache.get("third", "prompt"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_expired_entries_are_removed_and_missed() {
+        let (cache, dir) = temp_cache(Duration::ZERO);
+        cache.put("model", "prompt", "stale");
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(cache.get("model", "prompt"), None);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        assert_eq!(cache.stats().misses, 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    // Answers every prompt with the number of calls made so far
+    struct CountingClient(AtomicU64);
+
+    #[async_trait]
+    impl ModelClient for CountingClient {
+        async fn generate_contextual_thought(&self, _: &CellContext, _: &RealTimeContext, _: &str) -> Result<(String, f64, Vec<String>), Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn create_plan(&self, _: &[Thought]) -> Result<Plan, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn evaluate_dimensional_state(&self, _: &DimensionalPosition, _: &[Thought], _: &[Plan]) -> Result<(f64, f64), Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn compress_memories(&self, memories: &[String]) -> Result<String, Box<dyn Error>> {
+            self.query_llm(&memories.join("\n")).await
+        }
+
+        async fn gather_real_time_context(&self, _: Option<Vec<String>>) -> Result<RealTimeContext, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn generate_contextual_thoughts_batch(
+            &self,
+            _: &[(Uuid, &CellContext)],
+            _: &RealTimeContext,
+            _: &str,
+            _: &[Thought],
+        ) -> Result<HashMap<Uuid, Vec<(String, f64, Vec<String>)>>, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn query_llm(&self, _: &str) -> Result<String, Box<dyn Error>> {
+            Ok((self.0.fetch_add(1, Ordering::Relaxed) + 1).to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_client_replays_any_model_client() {
+        let (cache, dir) = temp_cache(Duration::from_secs(60));
+        let client = CachedClient::new(Box::new(CountingClient(AtomicU64::new(0))), "counting", cache);
+
+        assert_eq!(client.query_llm("prompt").await.unwrap(), "1");
+        assert_eq!(client.query_llm("PROMPT").await.unwrap(), "1");
+        let memories = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(client.compress_memories(&memories).await.unwrap(), "2");
+        assert_eq!(client.compress_memories(&memories).await.unwrap(), "2");
+
+        let stats = client.cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,235 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+//! Text embeddings used to compare thoughts by content.
+
+use crate::models::constants::{DEFAULT_EMBEDDING_BASE_URL, DEFAULT_EMBEDDING_MODEL};
+use async_trait::async_trait;
+use serde_json::json;
+use std::error::Error;
+
+#[async_trait]
+pub trait EmbeddingClient: Send + Sync {
+    /// Returns one vector per input text, in input order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>>;
+
+    fn model_name(&self) -> &str;
+}
+
+/// Any service exposing the OpenAI `/embeddings` endpoint.
+pub struct OpenAIEmbeddingClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+}
+
+impl OpenAIEmbeddingClient {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: DEFAULT_EMBEDDING_BASE_URL.to_string(),
+            api_key,
+            model: DEFAULT_EMBEDDING_MODEL.to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl EmbeddingClient for OpenAIEmbeddingClient {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut request = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .json(&json!({
+                "model": self.model,
+                "input": texts,
+            }));
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let json: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+        let data = json["data"].as_array().ok_or("Invalid embeddings response format")?;
+
+        let mut embeddings = vec![Vec::new(); texts.len()];
+        for (position, item) in data.iter().enumerate() {
+            let index = item["index"].as_u64().map_or(position, |i| i as usize);
+            let vector = parse_vector(&item["embedding"])?;
+            if let Some(slot) = embeddings.get_mut(index) {
+                *slot = vector;
+            }
+        }
+
+        if embeddings.iter().any(|e| e.is_empty()) {
+            return Err("Embeddings response is missing vectors".into());
+        }
+        Ok(embeddings)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+/// The local model server's `/embed` endpoint, next to `LocalLLMClient`'s `/generate`.
+pub struct LocalEmbeddingClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl LocalEmbeddingClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "http://localhost:8000".to_string(),
+        }
+    }
+}
+
+impl Default for LocalEmbeddingClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EmbeddingClient for LocalEmbeddingClient {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let json: serde_json::Value = self
+            .client
+            .post(format!("{}/embed", self.base_url))
+            .json(&json!({ "texts": texts }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let embeddings = json["embeddings"]
+            .as_array()
+            .ok_or("Invalid embeddings response format")?
+            .iter()
+            .map(parse_vector)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if embeddings.len() != texts.len() {
+            return Err(format!("Expected {} embeddings, got {}", texts.len(), embeddings.len()).into());
+        }
+        Ok(embeddings)
+    }
+
+    fn model_name(&self) -> &str {
+        "local"
+    }
+}
+
+fn parse_vector(value: &serde_json::Value) -> Result<Vec<f32>, Box<dyn Error>> {
+    value
+        .as_array()
+        .ok_or("Embedding is not an array")?
+        .iter()
+        .map(|v| v.as_f64().map(|f| f as f32).ok_or_else(|| "Embedding contains a non-number".into()))
+        .collect()
+}
+
+/// Cosine similarity in [-1, 1]; 0.0 for mismatched or zero-length vectors.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let mut dot = 0.0;
+    let mut norm_a = 0.0;
+    let mut norm_b = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+/// Maximal marginal relevance: greedily picks up to `limit` candidates, trading
+/// each candidate's `score` against its similarity to those already picked.
+/// `lambda` = 1.0 ranks purely by score. Candidates without an embedding are
+/// never penalised for similarity. Returns indices into `candidates`.
+pub fn select_diverse(candidates: &[(Option<&[f32]>, f64)], limit: usize, lambda: f64) -> Vec<usize> {
+    let mut selected: Vec<usize> = Vec::with_capacity(limit.min(candidates.len()));
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+
+    while selected.len() < limit && !remaining.is_empty() {
+        let (best_pos, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(pos, &i)| {
+                let redundancy = candidates[i].0.map_or(0.0, |embedding| {
+                    selected
+                        .iter()
+                        .filter_map(|&j| candidates[j].0)
+                        .map(|other| cosine_similarity(embedding, other) as f64)
+                        .fold(0.0, f64::max)
+                });
+                (pos, lambda * candidates[i].1 - (1.0 - lambda) * redundancy)
+            })
+            .fold((0, f64::NEG_INFINITY), |best, current| if current.1 > best.1 { current } else { best });
+
+        selected.push(remaining.remove(best_pos));
+    }
+
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_select_diverse_skips_near_duplicates() {
+        let a = [1.0, 0.0];
+        let a_copy = [0.99, 0.01];
+        let b = [0.0, 1.0];
+        let candidates = [(Some(&a[..]), 1.0), (Some(&a_copy[..]), 0.95), (Some(&b[..]), 0.6)];
+
+        assert_eq!(select_diverse(&candidates, 2, 0.5), vec![0, 2]);
+        assert_eq!(select_diverse(&candidates, 2, 1.0), vec![0, 1]);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::models::types::{CellContext, RealTimeContext, Thought, Plan, DimensionalPosition};
 use crate::api::model_client::ModelClient;
+use crate::api::prompts::render_prompt;
+use crate::models::plan_graph::parse_numbered_steps;
 use async_trait::async_trait;
 use chrono::Utc;
 use reqwest;
@@ -38,9 +40,11 @@ impl LocalLLMClient {
         // Parse the response
         let parsed: serde_json::Value = serde_json::from_str(&response_text)?;
         
-        Ok(parsed["response"].as_str()
+        let text = parsed["response"].as_str()
             .ok_or("Invalid response format")?
-            .to_string())
+            .to_string();
+
+        Ok(text)
     }
 }
 
@@ -52,20 +56,14 @@ impl ModelClient for LocalLLMClient {
         real_time_context: &RealTimeContext,
         colony_mission: &str,
     ) -> Result<(String, f64, Vec<String>), Box<dyn Error>> {
-        let prompt = format!(
-            r#"Generate a philosophical thought about this mission and context.
-Mission: {}
-Current Focus: {}
-Energy Level: {}
-
-Format your response exactly as:
-THOUGHT: [Your philosophical insight]
-RELEVANCE: [0.0-1.0]
-FACTORS: [Three factors, comma-separated]"#,
-            colony_mission,
-            cell_context.current_focus,
-            cell_context.energy_level
-        );
+        let energy_level = cell_context.energy_level.to_string();
+        let inbox = if cell_context.inbox.is_empty() { "None".to_string() } else { cell_context.inbox.join("\n") };
+        let prompt = render_prompt("local_thought", &[
+            ("mission", colony_mission),
+            ("current_focus", &cell_context.current_focus),
+            ("energy_level", &energy_level),
+            ("inbox", &inbox),
+        ])?;
 
         let response = self.generate_response(&prompt).await?;
         println!("Model response: {}", response);  // Debug print
@@ -108,35 +106,17 @@ FACTORS: [Three factors, comma-separated]"#,
     }
 
     async fn create_plan(&self, thoughts: &[Thought]) -> Result<Plan, Box<dyn Error>> {
-        let prompt = format!(
-            r#"Create a plan based on these thoughts:
-{}
+        let thoughts_context = thoughts.iter().map(|t| t.content.clone()).collect::<Vec<_>>().join("\n");
+        let prompt = render_prompt("local_plan", &[("thoughts_context", &thoughts_context)])?;
 
-Format response as:
-SUMMARY: [Plan summary]
-STEPS: [Numbered list of steps]
-SCORE: [0.0-1.0]"#,
-            thoughts.iter().map(|t| t.content.clone()).collect::<Vec<_>>().join("\n")
-        );
+        let response_text = self.generate_response(&prompt).await?;
 
-        let response = self.client
-            .post(&format!("{}/generate", self.base_url))
-            .json(&json!({
-                "prompt": prompt
-            }))
-            .send()
-            .await?;
-
-        let result: serde_json::Value = response.json().await?;
-        let response_text = result["response"].as_str()
-            .ok_or("Invalid response format")?;
-
-        // Create a basic plan structure
+        // Create a basic plan structure, one node per numbered step
         Ok(Plan {
             id: Uuid::new_v4(),
             thoughts: thoughts.to_vec(),
-            nodes: vec![],
-            summary: response_text.to_string(),
+            nodes: parse_numbered_steps(&response_text),
+            summary: response_text,
             score: 0.5,
             participating_cells: vec![],
             created_at: Utc::now(),
@@ -155,26 +135,10 @@ SCORE: [0.0-1.0]"#,
     }
 
     async fn compress_memories(&self, memories: &[String]) -> Result<String, Box<dyn Error>> {
-        let prompt = format!(
-            r#"Compress these memories into a concise summary:
-{}
+        let memories_text = memories.join("\n");
+        let prompt = render_prompt("local_memory_compression", &[("memories", &memories_text)])?;
 
-Format response as a single paragraph."#,
-            memories.join("\n")
-        );
-
-        let response = self.client
-            .post(&format!("{}/generate", self.base_url))
-            .json(&json!({
-                "prompt": prompt
-            }))
-            .send()
-            .await?;
-
-        let result: serde_json::Value = response.json().await?;
-        Ok(result["response"].as_str()
-            .ok_or("Invalid response format")?
-            .to_string())
+        self.generate_response(&prompt).await
     }
 
     async fn gather_real_time_context(
@@ -208,17 +172,6 @@ Format response as a single paragraph."#,
     }
 
     async fn query_llm(&self, prompt: &str) -> Result<String, Box<dyn Error>> {
-        let response = self.client
-            .post(&format!("{}/generate", self.base_url))
-            .json(&json!({
-                "prompt": prompt
-            }))
-            .send()
-            .await?;
-
-        let result: serde_json::Value = response.json().await?;
-        Ok(result["response"].as_str()
-            .ok_or("Invalid response format")?
-            .to_string())
+        self.generate_response(prompt).await
     }
 }
\ No newline at end of file
@@ -11,7 +11,13 @@ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLI
 pub mod openrouter;
 pub mod local_llm;
 pub mod model_client;
+pub mod prompts;
+pub mod cache;
+pub mod validation;
+pub mod embeddings;
 
 pub use model_client::ModelClient;
 pub use local_llm::LocalLLMClient;
-pub use openrouter::OpenRouterClient;
\ No newline at end of file
+pub use openrouter::OpenRouterClient;
+pub use cache::{CachedClient, ResponseCache};
+pub use embeddings::EmbeddingClient;
\ No newline at end of file
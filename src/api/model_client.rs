@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use std::error::Error;
 use crate::models::types::{CellContext, RealTimeContext, Thought, Plan, DimensionalPosition};
+use crate::api::cache::CacheStats;
 use uuid::Uuid;
 use std::collections::HashMap;
 
@@ -39,4 +40,9 @@ pub trait ModelClient: Send + Sync {
     ) -> Result<HashMap<Uuid, Vec<(String, f64, Vec<String>)>>, Box<dyn Error>>;
 
     async fn query_llm(&self, prompt: &str) -> Result<String, Box<dyn Error>>;
+
+    // Clients that opted into a response cache report its hit/miss counts
+    fn cache_stats(&self) -> Option<CacheStats> {
+        None
+    }
 }
\ No newline at end of file
@@ -21,12 +21,12 @@
 //! SOFTWARE.
 
 use crate::models::types::{
-    CellContext, RealTimeContext, Thought, Plan, PlanNode, PlanNodeStatus, PlanStatus,
+    CellContext, RealTimeContext, Thought, Plan,
     DimensionalPosition,
 };
 use crate::models::KnowledgeBase;
+use crate::models::plan_graph::parse_plan;
 use chrono::{DateTime, Utc};
-use rand::Rng;
 use reqwest;
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
@@ -35,6 +35,8 @@ use uuid::Uuid;
 use std::error::Error;  // Add this
 use async_trait::async_trait;  // Add this
 use crate::api::model_client::ModelClient;  // Add this
+use crate::api::prompts::render_prompt;
+use crate::api::validation::{is_dimension_line, validate_dimensional_analysis, validate_plan, validate_thought_batch, validate_with_repair, DimensionReadings};
 
 #[derive(Debug)]
 pub enum ClientError {
@@ -177,37 +179,7 @@ impl ModelClient for OpenRouterClient {
     }
 
     async fn query_llm(&self, prompt: &str) -> Result<String, Box<dyn Error>> {
-        // Generate random bool outside of async context
-        let use_grok = rand::random::<bool>();
-        
-        let model = if use_grok {
-            "x-ai/grok-beta"
-        } else {
-            "x-ai/grok-beta"
-        };
-
-        let response = self
-            .client
-            .post(&format!("{}/chat/completions", self.base_url))
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&serde_json::json!({
-                "model": model,
-                "messages": [{
-                    "role": "user",
-                    "content": prompt
-                }],
-                "temperature": 0.7,
-                "max_tokens": Self::get_max_tokens_for_model(model)
-            }))
-            .send()
-            .await?;
-
-        let json: serde_json::Value = response.json().await?;
-
-        Ok(json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
-            .to_string())
+        self.query_llm(prompt).await
     }
 }
 
@@ -260,7 +232,9 @@ impl OpenRouterClient {
         
         // Generate random values outside of async context
         let use_grok = rand::random::<bool>();
-        
+
+        let prompt = render_prompt("technical_developments", &[])?;
+
         // Add timeout
         let response = tokio::time::timeout(
             Duration::from_secs(30),
@@ -271,127 +245,7 @@ impl OpenRouterClient {
                     "model": if use_grok { "x-ai/grok-beta" } else { "x-ai/grok-beta" },
                     "messages": [{
                         "role": "user",
-                        "content": r#"
-                        Analyze technical developments from the last 72 hours across multiple domains.
-                        Focus on posts from accounts with <0.01% following on technical platforms.
-
-                        Required Analysis Vectors:
-
-                        1. TECHNICAL DEVELOPMENTS
-                        For each development:
-                        EVENT:
-                        TIMESTAMP: [Must be within last 72h, exact to minute]
-                        SOURCE: 
-                        - Repository URL + commit hash
-                        - Research paper DOI
-                        - Technical blog post URL
-                        - System deployment log
-                        MENTIONED BY: [Technical accounts only]
-                        - Individual researchers (<5k followers)
-                        - Research lab accounts
-                        - Open source maintainers
-                        - System architects
-                        - Technical leads
-                        TECHNICAL DETAILS:
-                        - Implementation specifics
-                        - Architecture changes
-                        - Performance metrics
-                        - Resource requirements
-                        - Integration points
-                        VALIDATION:
-                        - Reproducible results
-                        - Test coverage
-                        - Benchmark data
-                        - Error rates
-                        - System logs
-
-                        2. RESEARCH DEVELOPMENTS
-                        For each development:
-                        PAPER:
-                        TIMESTAMP: [Publication/preprint within 72h]
-                        DOI/arXiv:
-                        AUTHORS:
-                        INSTITUTION:
-                        KEY FINDINGS:
-                        - Methodology
-                        - Results
-                        - Limitations
-                        - Future work
-                        VALIDATION:
-                        - Experimental setup
-                        - Data collection
-                        - Statistical analysis
-                        - Reproducibility steps
-
-                        3. SYSTEM DEPLOYMENTS
-                        For each deployment:
-                        SYSTEM:
-                        TIMESTAMP: [Deployment within 72h]
-                        ORGANIZATION:
-                        SCALE:
-                        ARCHITECTURE:
-                        PERFORMANCE:
-                        - Latency metrics
-                        - Throughput data
-                        - Resource usage
-                        - Error rates
-                        VALIDATION:
-                        - Monitoring logs
-                        - Health metrics
-                        - Alert history
-                        - Recovery data
-
-                        4. TECHNICAL DISCUSSIONS
-                        For each significant thread:
-                        TOPIC:
-                        TIMESTAMP: [Discussion within 72h]
-                        PARTICIPANTS: [Technical roles only]
-                        KEY POINTS:
-                        - Technical challenges
-                        - Proposed solutions
-                        - Implementation details
-                        - Resource considerations
-                        VALIDATION:
-                        - Code examples
-                        - Benchmark results
-                        - Test cases
-                        - Performance data
-
-                        EVIDENCE REQUIREMENTS:
-                        1. Technical Validation
-                           - Public repository commits
-                           - Published papers
-                           - System logs
-                           - Performance metrics
-                           - Test results
-                           - Deployment data
-
-                        2. Source Requirements
-                           - Technical accounts only (<0.01% following)
-                           - Research institutions
-                           - Open source maintainers
-                           - System architects
-                           - Technical leads
-                           - Individual researchers
-
-                        3. Time Constraints
-                           - All events within last 72h
-                           - Exact timestamps required
-                           - Time zone specified
-                           - Update frequency noted
-
-                        4. Data Requirements
-                                                       - Raw metrics
-                           - Benchmark results
-                           - Error rates
-                           - Resource usage
-                           - System logs
-                           - Test coverage
-
-                        Return comprehensive analysis of developments from last 72h.
-                        Format as structured events with all required fields.
-                        Prioritize technical depth over quantity.
-                        "#
+                        "content": prompt
                     }],
                     "temperature": 0.9,
                     "max_tokens": Self::get_max_tokens_for_model("x-ai/grok-beta")
@@ -493,87 +347,16 @@ Recent colony thoughts:
             String::new()
         };
 
-        let context_query = format!(
-            r#"
-            System Analysis Framework:
-
-            CONTEXT:
-            {}
-            {}
-
-            1. POWER DYNAMICS ANALYSIS
-            - Disrupted hierarchies
-            - Emergent control mechanisms
-            - Resource flow shifts
-            - Influence network changes
-
-            2. SYSTEM BOUNDARIES ANALYSIS
-            - Interface mutations
-            - Boundary dissolutions
-            - Unexpected connections
-            - Integration points
-
-            3. TEMPORAL PATTERNS ANALYSIS
-            - Evolution trajectories
-            - Decay patterns
-            - Cyclic behaviors
-            - Timescale interactions
-
-            4. EMERGENCE ANALYSIS
-            - Unexpected properties
-            - Feedback loops
-            - Pattern formation
-            - System surprises
-
-            5. ASSUMPTION ANALYSIS
-            - Questionable constraints
-            - Hidden potentials
-            - Artificial limitations
-            - Missed connections
-
-            Required per analysis vector:
-            1. Evidence Trail:
-               - Active repository commits (72h)
-               - Researcher activities (72h)
-               - Experiment results (72h)
-               - Deployment metrics (72h)
-
-            2. Power Implications:
-               - Control shifts
-               - Resource reallocations
-               - Relationship changes
-               - Influence flows
-
-            3. System Effects:
-               - Boundary changes
-               - Interface formations
-               - Pattern emergences
-               - Capability evolutions
-
-            4. Technical Details:
-               - Architecture diagrams
-               - Integration points
-               - Data flows
-               - Control mechanisms
-
-            Format as:
-            VECTOR: [Analysis Type]
-            CONVENTIONAL VIEW: [Standard interpretation]
-            RADICAL INSIGHT: [Non-obvious observation]
-            EVIDENCE: [Concrete proof points]
-            IMPLICATIONS: [Cascading effects]
-            DIAGRAM: [ASCII representation]
-
-            Categories labeled and structured hierarchically.
-        "#,
-            trending_topics
-                .iter()
-                .map(|t| format!("- {}", t))
-                .collect::<Vec<_>>()
-                .join("
-"),
-            thoughts_context
-        );
+        let trending_context = trending_topics
+            .iter()
+            .map(|t| format!("- {}", t))
+            .collect::<Vec<_>>()
+            .join("
+");
+        let context_query = render_prompt("system_analysis", &[
+            ("trending_topics", &trending_context),
+            ("thoughts_context", &thoughts_context),
+        ])?;
 
         let response = self.query_llm(&context_query).await?;
         let parsed = self.parse_context_response(&response)?;
@@ -692,178 +475,79 @@ Knowledge Base Context:
             let cell_states = chunk
                 .iter()
                 .map(|(id, ctx)| {
+                    let inbox = if ctx.inbox.is_empty() {
+                        String::new()
+                    } else {
+                        format!("INBOX:\n{}\n", ctx.inbox.iter().map(|m| format!("- {}", m)).collect::<Vec<_>>().join("\n"))
+                    };
                     format!(
                         "### CELL {}
 FOCUS: {}
 ENERGY: {}
-",
+{}",
                         id,
                         ctx.current_focus,
-                        ctx.energy_level
+                        ctx.energy_level,
+                        inbox
                     )
                 })
                 .collect::<Vec<_>>()
                 .join("
 ");
 
-            let context_prompt = format!(
-                r#"Contextual Analysis Framework:
-
-                CURRENT OBJECTIVE: {}
-                KNOWLEDGE CONTEXT: {}
-
-                REAL-TIME CONTEXT:
-                Market Trends: {}
-                Tech Developments: {}
-                Current Events: {}
-                User Interactions: {}
-
-                CELL STATES:
-                {}
-
-                THOUGHT GENERATION RULES:
-                1. DO NOT DUPLICATE OR CLOSELY MIRROR ANY RECENT COLONY THOUGHTS
-                2. Each thought must explore completely new territory
-                3. If a thought seems similar to an existing one, take a radically different approach
-                4. Build upon but transform existing insights into novel directions
-                5. Explicitly avoid repeating:
-                   - Core concepts from recent thoughts
-                   - Similar conclusions or recommendations
-                   - Related technical approaches
-                   - Parallel solution paths
-
-                ENVIRONMENTAL SIGNALS:
-                1. SYSTEM DYNAMICS
-                   - Emerging patterns
-                   - Network effects
-                   - Adaptation signals
-                   - Behavioral shifts
-
-                2. EVOLUTIONARY VECTORS
-                   - Development paths
-                   - Growth patterns
-                   - Adaptation cycles
-                   - Scale dynamics
-
-                3. EMERGENCE INDICATORS
-                   - Novel properties
-                   - Pattern genesis
-                   - System innovations
-
-                4. BOUNDARY ANALYSIS
-                   - Current limits
-                   - Growth potential
-                   - System constraints
-                   - Connection opportunities
-
-                ENTITY STATES:
-                {}
-
-                Required Format (repeat for each cell):
-
-                ### CELL <uuid>
-
-                THOUGHT STRUCTURE:
-                1. OBSERVATION
-                   - Current state
-                   - Key patterns
-                   - System dynamics
-
-                2. ANALYSIS
-                   - Conventional wisdom
-                   - Hidden assumptions
-                   - Unexpected connections
-                   - Evidence trail
-
-                3. SYNTHESIS
-                   - Novel perspective
-                   - Strategic implications
-                   - Cascading effects
-                   - Action vectors
-
-                4. VISUALIZATION
-                   [ASCII representation showing:]
-                   - Core components
-                   - Dependencies
-                   - Data flows
-                   - Integration points
-                   - System boundaries
-                   - Feedback loops
-
-                Example format:
-                                                    [Core Goal]
-                                                        |
-                                    +-------------------+-------------------+
-                                    |                   |                   |
-                            [Component A]        [Component B]        [Component C]
-                                |                     |                    |
-                        +-------+-------+      +------+------+     +------+------+
-                        |       |       |      |      |      |     |      |      |
-                    [Task 1] [Task 2] [Task 3] ...  ...    ...   ...    ...    ...
-
-                Use box drawing characters: ─ │ ┌ ┐ └ ┘ ├ ┤ ┬ ┴ ┼
-
-                THOUGHT: [Core insight challenging assumptions] (500+ words)
-                RELEVANCE: <0.0-1.0>
-                FACTORS: [Exactly 3 key factors]"#,
-                colony_mission,
-                kb_context,
-                real_time_context
-                    .market_trends
-                    .first()
-                    .unwrap_or(&String::new()),
-                real_time_context
-                    .technological_developments
-                    .first()
-                    .unwrap_or(&String::new()),
-                real_time_context
-                    .current_events
-                    .first()
-                    .unwrap_or(&String::new()),
-                real_time_context
-                    .user_interactions
-                    .first()
-                    .unwrap_or(&String::new()),
-                cell_states,
-                cell_states  // Added this for ENTITY STATES section
-            );
+            let context_prompt = render_prompt("contextual_thoughts", &[
+                ("mission", colony_mission),
+                ("knowledge_context", &kb_context),
+                ("market_trends", real_time_context.market_trends.first().map(|s| s.as_str()).unwrap_or("")),
+                ("tech_developments", real_time_context.technological_developments.first().map(|s| s.as_str()).unwrap_or("")),
+                ("current_events", real_time_context.current_events.first().map(|s| s.as_str()).unwrap_or("")),
+                ("user_interactions", real_time_context.user_interactions.first().map(|s| s.as_str()).unwrap_or("")),
+                ("cell_states", &cell_states),
+            ])?;
 
             println!("
 ║ Processing sub-batch of {} cells", chunk.len());
-            match tokio::time::timeout(
+            let response = match tokio::time::timeout(
                 std::time::Duration::from_secs(100),
                 self.query_llm(&context_prompt),
             )
             .await
             {
-                Ok(Ok(response)) => {
-                    if let Ok(results) = self.parse_batch_thought_response(&response) {
-                        println!("║ Generated {} thoughts", results.len() * 3);
-                        for (id, thoughts) in &results {
-                            println!("║");
-                            println!("║ Cell {}", id);
-                            for (i, (thought, score, factors)) in thoughts.iter().enumerate() {
-                                println!("║ ├─ Thought {}", i + 1);
-                                println!("║ │  Score: {:.2}", score);
-                                println!("║ │  Factors:");
-                                for factor in factors {
-                                    println!("║ │    - {}", factor);
-                                }
-                                println!("║ │  Content: {:.100}...", thought);
-                            }
-                        }
-                        all_results.extend(results);
-                    } else {
-                        eprintln!("Failed to parse results from response:
-{}", response);
-                    }
-                }
+                Ok(Ok(response)) => response,
                 Ok(Err(e)) => {
                     eprintln!("Error in sub-batch: {}", e);
+                    continue;
                 }
                 Err(_) => {
                     eprintln!("Timeout in sub-batch 100s");
+                    continue;
+                }
+            };
+
+            let chunk_ids: Vec<Uuid> = chunk.iter().map(|(id, _)| *id).collect();
+            let response = validate_with_repair(self, response, "thought_format", |r| {
+                validate_thought_batch(r, &chunk_ids)
+            })
+            .await?;
+
+            if let Ok(results) = self.parse_batch_thought_response(&response) {
+                println!("║ Generated {} thoughts", results.len() * 3);
+                for (id, thoughts) in &results {
+                    println!("║");
+                    println!("║ Cell {}", id);
+                    for (i, (thought, score, factors)) in thoughts.iter().enumerate() {
+                        println!("║ ├─ Thought {}", i + 1);
+                        println!("║ │  Score: {:.2}", score);
+                        println!("║ │  Factors:");
+                        for factor in factors {
+                            println!("║ │    - {}", factor);
+                        }
+                        println!("║ │  Content: {:.100}...", thought);
+                    }
                 }
+                all_results.extend(results);
+            } else {
+                eprintln!("Failed to parse results from sub-batch response");
             }
         }
 
@@ -890,327 +574,26 @@ ENERGY: {}
                 .join("
 ");
 
-            let chunk_plan = self
-                .query_llm(&format!(
-                    r#"System Evolution Framework:
-
-    CONTEXT SIGNALS:
-    {}
-
-    Analyze each vector as a complex adaptive system:
-    1. NETWORK DYNAMICS
-    - Flow patterns
-    - Resource distribution
-    - Connection topology
-    - Interaction modes
-
-    2. BOUNDARY CONDITIONS
-    - Interface dynamics
-    - Connection patterns
-    - Integration vectors
-    - Barrier dissolution
-
-    3. EMERGENCE PATTERNS
-    - Novel properties
-    - Feedback cycles
-    - Pattern evolution
-    - System adaptations
-
-    4. POTENTIAL SPACES
-    - Unexplored capabilities
-    - Constraint removal
-    - Novel applications
-
-    Required Format:
-
-    SUMMARY: [Comprehensive system overview]
-
-    For each component:
-    COMPONENT: [Name]
-    CONVENTIONAL VIEW: [Standard approach]
-    RADICAL SHIFT: [New possibility]
-    EVIDENCE: [Proof points]
-        IMPLEMENTATION:
-    - Technical specifications
-    - Resource requirements
-    - Timeline estimates
-    - Success metrics
-    - Risk assessment
-
-    ARCHITECTURE:
-    [Detailed ASCII mind map showing:]
-    - Core components
-    - Dependencies
-    - Data flows
-    - Integration points
-    - System boundaries
-    - Feedback loops
-    
-    Example format:
-                                    [Core Goal]
-                                        |
-                    +-------------------+-------------------+
-                    |                   |                   |
-            [Component A]        [Component B]        [Component C]
-                |                     |                    |
-        +-------+-------+      +------+------+     +------+------+
-        |       |       |      |      |      |     |      |      |
-    [Task 1] [Task 2] [Task 3] ...  ...    ...   ...    ...    ...
-    
-    Use box drawing characters: ─ │ ┌ ┐ └ ┘ ├ ┤ ┬ ┴ ┼
-
-    INTEGRATION POINTS:
-    - System connections
-    - Data flows
-    - Control mechanisms
-    - Feedback loops"#,
-                    thoughts_context
-                ))
-                .await?;
+            let evolution_prompt = render_prompt("system_evolution", &[("thoughts_context", &thoughts_context)])?;
+            let chunk_plan = self.query_llm(&evolution_prompt).await?;
             
 
             consolidated_plans.push(chunk_plan);
         }
 
-        let combined_plan = self
-            .query_llm(&format!(
-                r#"System Integration Framework:
-
-    COMPONENT PLANS:
-    {}
-
-    1. POWER DYNAMICS ANALYSIS
-    - {{Control mechanisms}}
-    - {{Resource flows  }}
-    - {{Influence networks}} 
-    - {{Authority structures}}
-
-    2. SYSTEM BOUNDARIES ANALYSIS
-    - {{Interface points}}
-    - {{Connection patterns}}
-    - {{Integration opportunities}}
-    - {{Boundary dissolutions}}
-
-    3. EMERGENCE VECTORS ANALYSIS
-    - {{Unexpected properties}}
-    - {{Feedback loops}}
-    - {{Pattern formation}}
-    - {{System surprises}}
-
-    4. HIDDEN POTENTIALS ANALYSIS
-    - {{Untapped capabilities}}
-    - {{Novel applications}}
-    - {{Constraint removals}}
-    - {{Integration possibilities}}
-
-    Required Format:
-
-    MASTER PLAN:
-    [2000+ word comprehensive integration]
-
-    For each integration point:
-    INTERFACE: [Name]
-    CONVENTIONAL VIEW: [Standard approach]
-    RADICAL SHIFT: [New possibility]
-    EVIDENCE: [Proof points]
-    IMPLICATIONS: [Cascading effects]
-
-    ARCHITECTURE:
-    [Detailed system architecture in ASCII]
-
-    Requirements:
-    1. Logical component flow
-    2. Clear dependencies
-    3. Measurable outcomes
-    4. Risk mitigations
-    5. Resource allocations"#,
-                consolidated_plans.join("
+        let component_plans = consolidated_plans.join("
 
 === Next Component ===
 
-")
-            ))
-            .await?;
-
-        let enhanced_plan = self
-            .query_llm(&format!(
-                r#"Technical Integration Analysis Framework:
-
-    BASE PLAN:
-    {}
-
-    1. POWER DYNAMICS VECTORS
-    - Control shifts
-    - Resource reallocations
-    - Influence flows
-    - Authority transitions
-
-    2. SYSTEM BOUNDARIES
-    - Interface mutations
-    - Connection formations
-    - Integration emergences 
-    - Boundary dissolutions
-
-    3. TEMPORAL PATTERNS
-    - Evolution trajectories
-    - Decay patterns
-    - Cyclic behaviors
-    - Timescale interactions
-
-    4. EMERGENT PROPERTIES
-    - Unexpected capabilities
-    - Feedback loops
-    - Pattern formations
-    - System surprises
-
-    Required Technical Analysis:
-    1. Recent Developments (72h)
-       - Commit activities
-       - Research publications
-       - Experiment results
-       - Deployment metrics
-
-    2. Integration Points
-       - System interfaces
-       - Data flows
-       - Control mechanisms
-       - Feedback systems
-
-    3. Resource Requirements
-       - Computational needs
-       - Storage demands
-       - Network capacity
-       - Processing power
-
-    4. Performance Metrics
-       - Response times
-       - Throughput rates
-       - Error margins
-       - Recovery speeds
-
-    Format each component with:
-    COMPONENT: [Name]
-    TECHNICAL_BASELINE: [Current state]
-    ENHANCEMENT_VECTOR: [Improvement path]
-    EVIDENCE: [Proof points]
-    METRICS: [Success measures]
-
-    Requirements:
-    1. 2000+ words
-    2. RFP structure
-    3. Technical precision
-    4. Implementation focus"#,
-                combined_plan
-            ))
-            .await?;
-
-        let mut nodes = Vec::new();
-        let mut current_node = None;
-        let mut summary = String::new();
-        let mut in_summary = false;
-        let mut in_components = false;
-
-        // Ensure we have at least one default node if none are parsed
-        let default_node = PlanNode {
-            id: Uuid::new_v4(),
-            title: "Initial System Analysis".to_string(),
-            description: "Analyze current system state and identify key improvement vectors".to_string(),
-            dependencies: Vec::new(),
-            estimated_completion: 0.2,
-            status: PlanNodeStatus::Pending,
-        };
-        nodes.push(default_node);
-
-        for line in enhanced_plan.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-
-            if line.starts_with("SUMMARY:") {
-                in_summary = true;
-                summary = line.trim_start_matches("SUMMARY:").trim().to_string();
-            } else if in_summary && !line.starts_with("COMPONENTS:") {
-                summary.push_str(" ");
-                summary.push_str(line);
-            } else if line.starts_with("COMPONENTS:") || 
-                      line.starts_with("IMPLEMENTATION:") ||
-                      line.starts_with("TECHNICAL COMPONENTS:") {
-                in_summary = false;
-                in_components = true;
-            } else if in_components {
-                if line.starts_with(|c: char| c.is_digit(10)) || 
-                   line.starts_with("COMPONENT:") ||
-                   line.starts_with("INTERFACE:") {
-                    if let Some(node) = current_node {
-                        nodes.push(node);
-                    }
-
-                    let title = line
-                        .trim_start_matches(|c: char| c.is_digit(10) || c == '.' || c == ':')
-                        .trim_start_matches("COMPONENT")
-                        .trim_start_matches("INTERFACE")
-                        .trim()
-                        .to_string();
-
-                    current_node = Some(PlanNode {
-                        id: Uuid::new_v4(),
-                        title,
-                        description: String::new(),
-                        dependencies: Vec::new(),
-                        estimated_completion: rand::thread_rng().gen_range(0.2..0.4),
-                        status: PlanNodeStatus::Pending,
-                    });
-                } else if (line.starts_with('-') || line.starts_with("DESCRIPTION:")) && current_node.is_some() {
-                    if let Some(ref mut node) = current_node {
-                        if !node.description.is_empty() {
-                            node.description.push_str("
 ");
-                        }
-                        node.description.push_str(line
-                            .trim_start_matches('-')
-                            .trim_start_matches("DESCRIPTION:")
-                            .trim());
-                    }
-                }
-            }
-        }
-
-        if let Some(node) = current_node {
-            nodes.push(node);
-        }
+        let integration_prompt = render_prompt("system_integration", &[("component_plans", &component_plans)])?;
+        let combined_plan = self.query_llm(&integration_prompt).await?;
 
-        if nodes.len() == 1 && !summary.is_empty() {
-            for line in summary.lines() {
-                if line.contains(':') || line.contains('-') {
-                    nodes.push(PlanNode {
-                        id: Uuid::new_v4(),
-                        title: line.split(':').next().unwrap_or(line).trim().to_string(),
-                        description: line.split(':').nth(1).unwrap_or("").trim().to_string(),
-                        dependencies: Vec::new(),
-                        estimated_completion: rand::thread_rng().gen_range(0.2..0.4),
-                        status: PlanNodeStatus::Pending,
-                    });
-                }
-            }
-        }
+        let enhanced_prompt = render_prompt("technical_integration", &[("base_plan", &combined_plan)])?;
+        let enhanced_plan = self.query_llm(&enhanced_prompt).await?;
+        let enhanced_plan = validate_with_repair(self, enhanced_plan, "plan_format", validate_plan).await?;
 
-        let score = if !nodes.is_empty() {
-            nodes.iter().map(|n| n.estimated_completion).sum::<f64>() / nodes.len() as f64
-        } else {
-            0.0
-        };
-
-        Ok(Plan {
-            id: Uuid::new_v4(),
-            thoughts: thoughts.to_vec(),
-            nodes,
-            summary,
-            score,
-            participating_cells: Vec::new(),
-            created_at: Utc::now(),
-            status: PlanStatus::Proposed,
-        })
+        Ok(parse_plan(&enhanced_plan, thoughts)?)
     }
 
     pub async fn evaluate_dimensional_state(
@@ -1233,112 +616,23 @@ ENERGY: {}
             .join("
 ");
 
-        let eval_prompt = format!(
-            r#"Dimensional Analysis Framework:
-
-    CURRENT STATE:
-    Emergence: {:.2}
-    Coherence: {:.2}
-    Resilience: {:.2}
-    Intelligence: {:.2}
-    Efficiency: {:.2}
-    Integration: {:.2}
-
-    CONTEXT:
-    THOUGHTS:
-    {}
-
-    PLANS:
-    {}
-
-    Analysis Vectors:
-
-    Fill out each of the items on here in depth just like a CEO applies to take a company public.
-    1. POWER DYNAMICS ANALYSIS
-    - {{Control mechanisms}}
-    - {{Resource flows  }}
-    - {{Influence networks}} 
-    - {{Authority structures}}
-
-    2. SYSTEM BOUNDARIES ANALYSIS
-    - {{Interface points}}
-    - {{Connection patterns}}
-    - {{Integration opportunities}}
-    - {{Boundary dissolutions}}
-
-    3. EMERGENCE VECTORS ANALYSIS
-    - {{Unexpected properties}}
-    - {{Feedback loops}}
-    - {{Pattern formation}}
-    - {{System surprises}}
-
-    4. HIDDEN POTENTIALS ANALYSIS
-    - {{Untapped capabilities that could result from this plan}}
-    - Removed constraints
-    - {{Novel applications of the plans}}
-
-    Required Analysis Format:
-
-    DIMENSIONAL_SCORES:
-    For each dimension:
-    DIMENSION: [Name]
-    CONVENTIONAL VIEW: [Standard assessment]
-    RADICAL INSIGHT: [Non-obvious observation]
-    EVIDENCE: [Concrete proof points]
-    SCORE: <-100 to 100>
-    IMPLICATIONS: [Cascading effects]
-
-    ENERGY_DYNAMICS:
-    CURRENT_STATE: [Assessment]
-    SHIFT_VECTOR: [Direction]
-    MAGNITUDE: <-100 to 100>
-    EVIDENCE: [Proof points]
-
-    DOPAMINE_DYNAMICS:
-    ENGAGEMENT_PATTERN: [Assessment]
-    REINFORCEMENT_VECTOR: [Direction]
-    MAGNITUDE: <0.0 to 1.0>
-    EVIDENCE: [Proof points]
-
-    Requirements:
-    1. Evidence-based scoring
-    2. Multi-framework analysis
-    3. Pattern recognition
-    4. Emergence identification
-    5. System dynamics mapping"#,
-            dimensional_position.emergence,
-            dimensional_position.coherence,
-            dimensional_position.resilience,
-            dimensional_position.intelligence,
-            dimensional_position.efficiency,
-            dimensional_position.integration,
-            thoughts_context,
-            plans_context
-        );
+        let eval_prompt = render_prompt("dimensional_analysis", &[
+            ("emergence", &format!("{:.2}", dimensional_position.emergence)),
+            ("coherence", &format!("{:.2}", dimensional_position.coherence)),
+            ("resilience", &format!("{:.2}", dimensional_position.resilience)),
+            ("intelligence", &format!("{:.2}", dimensional_position.intelligence)),
+            ("efficiency", &format!("{:.2}", dimensional_position.efficiency)),
+            ("integration", &format!("{:.2}", dimensional_position.integration)),
+            ("thoughts_context", &thoughts_context),
+            ("plans_context", &plans_context),
+        ])?;
 
         let response = self.query_llm(&eval_prompt).await?;
+        let response = validate_with_repair(self, response, "dynamics_format", validate_dimensional_analysis).await?;
 
-        let mut energy_impact = 0.0;
-        let mut dopamine_impact = 0.5;
-
-        for line in response.lines() {
-            let line = line.trim();
-            if line.starts_with("MAGNITUDE:") {
-                if energy_impact == 0.0 {
-                    energy_impact = line
-                        .trim_start_matches("MAGNITUDE:")
-                        .trim()
-                        .parse()
-                        .unwrap_or(0.0);
-                } else {
-                    dopamine_impact = line
-                        .trim_start_matches("MAGNITUDE:")
-                        .trim()
-                        .parse()
-                        .unwrap_or(0.5);
-                }
-            }
-        }
+        let readings = DimensionReadings::parse_dynamics(&response);
+        let energy_impact = readings.energy.unwrap_or(0.0);
+        let dopamine_impact = readings.dopamine.unwrap_or(0.5);
 
         Ok((energy_impact, dopamine_impact))
     }
@@ -1349,52 +643,7 @@ ENERGY: {}
     ) -> Result<String, Box<dyn std::error::Error>> {
         let memories_text = memories.join("
 ");
-        let prompt = format!(
-            r#"Memory Compression Framework:
-
-    CONTENT:
-    {}
-
-    Analysis Vectors:
-    1. CORE PATTERNS
-       - Recurring themes
-       - Common elements
-       - Shared structures
-
-    2. RELATIONSHIP MAPPING
-       - Direct connections
-       - Indirect links
-       - Hidden dependencies
-
-    3. KNOWLEDGE SYNTHESIS
-       - Key insights
-       - Critical learnings
-       - Fundamental principles
-
-    4. STRATEGIC IMPORTANCE
-       - Action triggers
-       - Decision points
-       - Resource implications
-
-    Required Format:
-    PATTERN: [Identified pattern]
-    EVIDENCE: [Supporting data]
-    IMPLICATIONS: [Strategic impact]
-    ACTIONABILITY: [Implementation path]
-
-    Organize by:
-    1. Impact magnitude
-    2. Implementation feasibility
-    3. Resource requirements
-    4. Time sensitivity
-
-    Focus on:
-    1. Pattern emergence
-    2. Strategic relevance
-    3. Actionable insights
-    4. Critical dependencies"#,
-            memories_text
-        );
+        let prompt = render_prompt("memory_compression", &[("memories", &memories_text)])?;
 
         self.query_llm(&prompt).await
     }
@@ -1403,52 +652,7 @@ ENERGY: {}
         &self,
         content: &str,
     ) -> Result<String, Box<dyn std::error::Error>> {
-        let prompt = format!(
-            r#"Knowledge Synthesis Framework:
-
-    CONTENT:
-    {}
-
-    Analysis Vectors:
-    1. CORE CONCEPTS
-       - Fundamental principles
-       - Key methodologies
-       - Critical patterns
-
-    2. RELATIONSHIP MAPPING
-       - Direct connections
-       - Indirect links
-       - Hidden dependencies
-
-    3. PATTERN RECOGNITION
-       - Recurring themes
-       - Common structures
-       - Shared elements
-
-    4. STRATEGIC RELEVANCE
-       - Action implications
-       - Decision impacts
-       - Resource requirements
-
-    Required Format:
-    CONCEPT: [Core concept]
-    EVIDENCE: [Supporting data]
-    CONNECTIONS: [Related elements]
-    IMPLICATIONS: [Strategic impact]
-
-    Organize by:
-    1. Impact magnitude
-    2. Implementation relevance
-    3. Resource implications
-    4. Time sensitivity
-
-    Preserve:
-    1. Technical accuracy
-    2. Source references
-    3. Critical details
-    4. Implementation paths"#,
-            content
-        );
+        let prompt = render_prompt("knowledge_synthesis", &[("content", content)])?;
 
         self.query_llm(&prompt).await
     }
@@ -1505,6 +709,7 @@ ENERGY: {}
         } else {
             "x-ai/grok-beta"
         };
+
     
         let response = self
             .client
@@ -1523,7 +728,6 @@ ENERGY: {}
             .await?;
     
         let json: serde_json::Value = response.json().await?;
-    
         Ok(json["choices"][0]["message"]["content"]
             .as_str()
             .unwrap_or("")
@@ -1584,6 +788,7 @@ ENERGY: {}
             let mut current_factors = Vec::new();
             let mut in_thought = false;
             let mut thought_buffer = String::new();
+            let mut dimension_lines = Vec::new();
 
             while let Some(line) = lines.next() {
                 let line = line.trim();
@@ -1602,6 +807,12 @@ ENERGY: {}
                     continue;
                 }
 
+                // Keep dimension readings together so they survive however the section is ordered
+                if is_dimension_line(line) {
+                    dimension_lines.push(line.to_string());
+                    continue;
+                }
+
                 // Parse thought sections and other components
                 if line.starts_with("**THOUGHT STRUCTURE:**") {
                     in_thought = true;
@@ -1724,6 +935,11 @@ ENERGY: {}
                 current_thought = thought_buffer.clone();
             }
 
+            if !current_thought.is_empty() && !dimension_lines.is_empty() {
+                current_thought.push_str("\n\n");
+                current_thought.push_str(&dimension_lines.join("\n"));
+            }
+
             if let Some(uuid) = current_uuid {
                 if !current_thought.is_empty() {
                                         results.entry(uuid)
@@ -1735,11 +951,7 @@ ENERGY: {}
 
         // Improved error reporting
         if results.is_empty() {
-            eprintln!("Warning: Failed to parse any thoughts from response");
-            eprintln!("Response structure:");
-            for (i, line) in response.lines().enumerate() {
-                eprintln!("{:3}: {}", i + 1, line);
-            }
+            eprintln!("Warning: Failed to parse any thoughts from response ({} lines)", response.lines().count());
         }
 
         Ok(results)
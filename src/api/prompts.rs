@@ -0,0 +1,187 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+//! Named prompt templates shared by every `ModelClient`.
+//!
+//! Templates use `{{variable}}` placeholders. The built-in defaults are compiled
+//! from the `prompts/` directory; any `<name>.txt` file found in the prompts
+//! directory at runtime overrides the default of the same name.
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+pub const DEFAULT_PROMPTS_DIR: &str = "prompts";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("technical_developments", include_str!("../../prompts/technical_developments.txt")),
+    ("system_analysis", include_str!("../../prompts/system_analysis.txt")),
+    ("contextual_thoughts", include_str!("../../prompts/contextual_thoughts.txt")),
+    ("system_evolution", include_str!("../../prompts/system_evolution.txt")),
+    ("system_integration", include_str!("../../prompts/system_integration.txt")),
+    ("technical_integration", include_str!("../../prompts/technical_integration.txt")),
+    ("dimensional_analysis", include_str!("../../prompts/dimensional_analysis.txt")),
+    ("memory_compression", include_str!("../../prompts/memory_compression.txt")),
+    ("knowledge_synthesis", include_str!("../../prompts/knowledge_synthesis.txt")),
+    ("plan_news_query", include_str!("../../prompts/plan_news_query.txt")),
+    ("local_thought", include_str!("../../prompts/local_thought.txt")),
+    ("local_plan", include_str!("../../prompts/local_plan.txt")),
+    ("local_memory_compression", include_str!("../../prompts/local_memory_compression.txt")),
+    ("response_repair", include_str!("../../prompts/response_repair.txt")),
+    ("thought_format", include_str!("../../prompts/thought_format.txt")),
+    ("plan_format", include_str!("../../prompts/plan_format.txt")),
+    ("dynamics_format", include_str!("../../prompts/dynamics_format.txt")),
+    ("memory_merge", include_str!("../../prompts/memory_merge.txt")),
+    ("plan_step_review", include_str!("../../prompts/plan_step_review.txt")),
+    ("plan_merge", include_str!("../../prompts/plan_merge.txt")),
+];
+
+#[derive(Clone, Debug)]
+pub struct PromptRegistry {
+    templates: HashMap<String, String>,
+}
+
+impl PromptRegistry {
+    pub fn with_defaults() -> Self {
+        Self {
+            templates: BUILTIN_TEMPLATES
+                .iter()
+                .map(|(name, template)| (name.to_string(), template.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Loads every `<name>.txt` in `path`, replacing templates with the same name.
+    /// Returns the number of templates loaded; a missing directory loads nothing.
+    pub fn load_dir(&mut self, path: &Path) -> Result<usize, std::io::Error> {
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let mut loaded = 0;
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("txt") {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) {
+                let template = fs::read_to_string(&path)?;
+                self.templates.insert(name, template);
+                loaded += 1;
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(|t| t.as_str())
+    }
+
+    pub fn render(&self, name: &str, vars: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+        let template = self
+            .get(name)
+            .ok_or_else(|| format!("Unknown prompt template '{}'", name))?;
+
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+
+            match after.find("}}") {
+                Some(end) if is_variable_name(&after[..end]) => {
+                    let key = &after[..end];
+                    let value = vars
+                        .iter()
+                        .find(|(k, _)| *k == key)
+                        .map(|(_, v)| *v)
+                        .ok_or_else(|| format!("Missing value for '{}' in prompt template '{}'", key, name))?;
+                    rendered.push_str(value);
+                    rest = &after[end + 2..];
+                }
+                _ => {
+                    rendered.push_str("{{");
+                    rest = after;
+                }
+            }
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
+    }
+}
+
+fn is_variable_name(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+lazy_static! {
+    static ref PROMPT_REGISTRY: RwLock<PromptRegistry> = {
+        let mut registry = PromptRegistry::with_defaults();
+        if let Err(e) = registry.load_dir(Path::new(DEFAULT_PROMPTS_DIR)) {
+            eprintln!("Error loading prompt templates from {}: {}", DEFAULT_PROMPTS_DIR, e);
+        }
+        RwLock::new(registry)
+    };
+}
+
+/// Renders a registered template, substituting each `{{key}}` with its value.
+pub fn render_prompt(name: &str, vars: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+    PROMPT_REGISTRY
+        .read()
+        .map_err(|e| format!("Prompt registry lock error: {}", e))?
+        .render(name, vars)
+}
+
+/// Overrides templates from another directory, e.g. a per-mission prompt set.
+pub fn load_prompt_dir(path: &str) -> Result<usize, Box<dyn Error>> {
+    let dir = Path::new(path);
+    if !dir.is_dir() {
+        return Err(format!("Prompt directory {} does not exist", path).into());
+    }
+
+    let mut registry = PROMPT_REGISTRY
+        .write()
+        .map_err(|e| format!("Prompt registry lock error: {}", e))?;
+    Ok(registry.load_dir(dir)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        let registry = PromptRegistry::with_defaults();
+        let rendered = registry
+            .render("memory_compression", &[("memories", "first memory")])
+            .unwrap();
+
+        assert!(rendered.contains("first memory"));
+        assert!(!rendered.contains("{{memories}}"));
+        assert!(registry.render("memory_compression", &[]).is_err());
+    }
+
+    #[test]
+    fn test_render_keeps_literal_braces() {
+        let registry = PromptRegistry::with_defaults();
+        let rendered = registry
+            .render("system_integration", &[("component_plans", "plan")])
+            .unwrap();
+
+        assert!(rendered.contains("{Control mechanisms}"));
+    }
+}
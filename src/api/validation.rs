@@ -0,0 +1,393 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+//! Checks LLM responses for the fields the colony parses and asks the model to
+//! repair responses that are missing them.
+
+use crate::api::model_client::ModelClient;
+use crate::api::prompts::render_prompt;
+use crate::models::constants::{MAX_REPAIR_ATTEMPTS, MAX_REPAIR_RESPONSE_CHARS};
+use crate::models::plan_graph::parse_plan_nodes;
+use crate::models::types::DimensionalPosition;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+/// Axis labels in the order of `DimensionReadings::axes`.
+pub const DIMENSION_AXES: [&str; 6] = [
+    "EMERGENT_INTELLIGENCE",
+    "RESOURCE_EFFICIENCY",
+    "NETWORK_COHERENCE",
+    "GOAL_ALIGNMENT",
+    "TEMPORAL_RESILIENCE",
+    "DIMENSIONAL_INTEGRATION",
+];
+
+/// Dimension, energy and dopamine values read from a response. Fields the model
+/// left out or wrote in an unreadable form stay `None` rather than defaulting.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DimensionReadings {
+    pub axes: [Option<f64>; 6],
+    pub energy: Option<f64>,
+    pub dopamine: Option<f64>,
+}
+
+impl DimensionReadings {
+    pub fn parse(text: &str) -> Self {
+        let mut readings = Self::default();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(value) = line.strip_prefix("DOPAMINE:") {
+                readings.dopamine = parse_score(value).filter(|v| (0.0..=1.0).contains(v));
+                continue;
+            }
+
+            let Some(entry) = line.strip_prefix('-') else { continue };
+            let Some((label, value)) = entry.split_once(':') else { continue };
+            if let Some(index) = DIMENSION_AXES.iter().position(|axis| *axis == label.trim()) {
+                readings.axes[index] = parse_score(value);
+            }
+        }
+
+        readings
+    }
+
+    /// Reads the `MAGNITUDE:` lines of a dimensional analysis: the one under
+    /// `ENERGY_DYNAMICS` as energy and the one under `DOPAMINE_DYNAMICS` as
+    /// dopamine. Without section headers the first is energy, the second dopamine.
+    pub fn parse_dynamics(text: &str) -> Self {
+        let mut readings = Self::default();
+        let mut in_dopamine = false;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with("ENERGY_DYNAMICS") {
+                in_dopamine = false;
+            } else if line.starts_with("DOPAMINE_DYNAMICS") {
+                in_dopamine = true;
+            } else if let Some(value) = line.strip_prefix("MAGNITUDE:") {
+                if in_dopamine || readings.energy.is_some() {
+                    readings.dopamine = parse_score(value).filter(|v| (0.0..=1.0).contains(v));
+                } else {
+                    readings.energy = parse_score(value).filter(|v| (-100.0..=100.0).contains(v));
+                }
+            }
+        }
+
+        readings
+    }
+
+    pub fn missing_axes(&self) -> Vec<&'static str> {
+        DIMENSION_AXES
+            .iter()
+            .zip(self.axes.iter())
+            .filter(|(_, value)| value.is_none())
+            .map(|(axis, _)| *axis)
+            .collect()
+    }
+
+    /// Writes every axis that was read, leaving the others at their current value.
+    pub fn apply_to(&self, position: &mut DimensionalPosition) {
+        let targets = [
+            &mut position.emergence,
+            &mut position.efficiency,
+            &mut position.coherence,
+            &mut position.intelligence,
+            &mut position.resilience,
+            &mut position.integration,
+        ];
+
+        for (target, value) in targets.into_iter().zip(self.axes.iter()) {
+            if let Some(value) = value {
+                *target = value.clamp(-100.0, 100.0);
+            }
+        }
+    }
+}
+
+/// True for the `DIMENSIONS:`, axis and `DOPAMINE:` lines of a thought section.
+pub fn is_dimension_line(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("DIMENSIONS:")
+        || line.starts_with("DOPAMINE:")
+        || line
+            .strip_prefix('-')
+            .and_then(|entry| entry.split_once(':'))
+            .is_some_and(|(label, _)| DIMENSION_AXES.contains(&label.trim()))
+}
+
+// Accepts "42", "+42", "0.7 (high)" or "[0.7]", but not prose
+fn parse_score(value: &str) -> Option<f64> {
+    value
+        .split_whitespace()
+        .next()?
+        .trim_matches(|c: char| c == '[' || c == ']' || c == ',' || c == '*')
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+}
+
+// Where a cell's own header sits, so UUIDs cited in another cell's
+// REFERENCES line don't split the response in the wrong place
+fn section_start(response: &str, id: &Uuid) -> Option<usize> {
+    let id = id.to_string();
+    let mut offset = 0;
+    for line in response.split_inclusive('\n') {
+        if line.contains("CELL") {
+            if let Some(pos) = line.find(&id) {
+                return Some(offset + pos);
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Lists what is missing from a batched thought response, per expected cell.
+pub fn validate_thought_batch(response: &str, cell_ids: &[Uuid]) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let mut starts: Vec<(usize, Uuid)> = cell_ids
+        .iter()
+        .filter_map(|id| section_start(response, id).map(|pos| (pos, *id)))
+        .collect();
+    starts.sort_by_key(|(pos, _)| *pos);
+
+    for id in cell_ids {
+        if !starts.iter().any(|(_, found)| found == id) {
+            issues.push(format!("CELL {}: section is missing", id));
+        }
+    }
+
+    for (i, (start, id)) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).map_or(response.len(), |(next, _)| *next);
+        let section = &response[*start..end];
+
+        let relevance = section
+            .lines()
+            .find_map(|line| line.trim().trim_start_matches('*').strip_prefix("RELEVANCE:"))
+            .and_then(parse_score);
+        if !relevance.is_some_and(|r| (0.0..=1.0).contains(&r)) {
+            issues.push(format!("CELL {}: RELEVANCE must be a number between 0.0 and 1.0", id));
+        }
+
+        let readings = DimensionReadings::parse(section);
+        let missing = readings.missing_axes();
+        if !missing.is_empty() {
+            issues.push(format!("CELL {}: DIMENSIONS is missing a numeric value for {}", id, missing.join(", ")));
+        }
+        if readings.dopamine.is_none() {
+            issues.push(format!("CELL {}: DOPAMINE must be a number between 0.0 and 1.0", id));
+        }
+    }
+
+    issues
+}
+
+/// A plan needs at least one named `COMPONENT:` block to produce plan nodes.
+pub fn validate_plan(response: &str) -> Vec<String> {
+    let has_component = response.lines().any(|line| {
+        line.trim()
+            .strip_prefix("COMPONENT:")
+            .map(|name| name.trim().trim_matches(|c| c == '[' || c == ']'))
+            .is_some_and(|name| !name.is_empty() && name != "Name")
+    });
+
+    if !has_component {
+        return vec!["No COMPONENT: blocks found; each component must start with a `COMPONENT: <name>` line".to_string()];
+    }
+    match parse_plan_nodes(response) {
+        Ok(_) => Vec::new(),
+        Err(cycle) => vec![format!("{}; DEPENDS_ON lines must not depend on each other in a loop", cycle)],
+    }
+}
+
+/// Problems with the energy and dopamine magnitudes of a dimensional analysis.
+pub fn validate_dimensional_analysis(response: &str) -> Vec<String> {
+    let readings = DimensionReadings::parse_dynamics(response);
+    let mut issues = Vec::new();
+    if readings.energy.is_none() {
+        issues.push("ENERGY_DYNAMICS: MAGNITUDE must be a number between -100 and 100".to_string());
+    }
+    if readings.dopamine.is_none() {
+        issues.push("DOPAMINE_DYNAMICS: MAGNITUDE must be a number between 0.0 and 1.0".to_string());
+    }
+    issues
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct RepairStats {
+    pub checked: u64,
+    pub malformed: u64,
+    pub repaired: u64,
+    #[serde(default)]
+    pub attempts: u64, // Repair prompts sent to the model
+}
+
+impl RepairStats {
+    pub fn success_rate(&self) -> f64 {
+        if self.malformed > 0 {
+            self.repaired as f64 / self.malformed as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Default)]
+struct RepairCounters {
+    checked: AtomicU64,
+    malformed: AtomicU64,
+    repaired: AtomicU64,
+    attempts: AtomicU64,
+}
+
+lazy_static! {
+    static ref REPAIR_COUNTERS: RepairCounters = RepairCounters::default();
+}
+
+pub fn repair_stats() -> RepairStats {
+    RepairStats {
+        checked: REPAIR_COUNTERS.checked.load(Ordering::Relaxed),
+        malformed: REPAIR_COUNTERS.malformed.load(Ordering::Relaxed),
+        repaired: REPAIR_COUNTERS.repaired.load(Ordering::Relaxed),
+        attempts: REPAIR_COUNTERS.attempts.load(Ordering::Relaxed),
+    }
+}
+
+/// Validates `response` and, if it has problems, asks the model to fix them up
+/// to `MAX_REPAIR_ATTEMPTS` times. `format_template` names the prompt template
+/// describing the expected layout. When every attempt fails, the response with
+/// the fewest problems is returned so callers can still use what did parse.
+/// Every repair prompt counts towards `RepairStats::attempts`, which callers
+/// compare before and after a model call to bill the cells it was made for.
+pub async fn validate_with_repair<F>(
+    client: &dyn ModelClient,
+    response: String,
+    format_template: &str,
+    validate: F,
+) -> Result<String, Box<dyn Error>>
+where
+    F: Fn(&str) -> Vec<String> + Send + Sync,
+{
+    REPAIR_COUNTERS.checked.fetch_add(1, Ordering::Relaxed);
+    let mut issues = validate(&response);
+    if issues.is_empty() {
+        return Ok(response);
+    }
+
+    REPAIR_COUNTERS.malformed.fetch_add(1, Ordering::Relaxed);
+    let format = render_prompt(format_template, &[])?;
+    let mut best = response;
+
+    for attempt in 1..=MAX_REPAIR_ATTEMPTS {
+        let truncated: String = best.chars().take(MAX_REPAIR_RESPONSE_CHARS).collect();
+        let repair_prompt = render_prompt("response_repair", &[
+            ("issues", &issues.iter().map(|i| format!("- {}", i)).collect::<Vec<_>>().join("\n")),
+            ("format", &format),
+            ("response", &truncated),
+        ])?;
+
+        REPAIR_COUNTERS.attempts.fetch_add(1, Ordering::Relaxed);
+        let repaired = match client.query_llm(&repair_prompt).await {
+            Ok(repaired) => repaired,
+            Err(e) => {
+                eprintln!("Repair attempt {} failed: {}", attempt, e);
+                break;
+            }
+        };
+
+        let remaining = validate(&repaired);
+        if remaining.is_empty() {
+            REPAIR_COUNTERS.repaired.fetch_add(1, Ordering::Relaxed);
+            return Ok(repaired);
+        }
+        if remaining.len() < issues.len() {
+            best = repaired;
+            issues = remaining;
+        }
+    }
+
+    eprintln!("Warning: response still malformed after {} repair attempts:", MAX_REPAIR_ATTEMPTS);
+    for issue in &issues {
+        eprintln!("  - {}", issue);
+    }
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readings_leave_unparsed_axes_unset() {
+        let readings = DimensionReadings::parse(
+            "DIMENSIONS:\n- EMERGENT_INTELLIGENCE: 42\n- NETWORK_COHERENCE: high\n- GOAL_ALIGNMENT: -12.5 (rising)\nDOPAMINE: 0.8",
+        );
+
+        assert_eq!(readings.axes[0], Some(42.0));
+        assert_eq!(readings.axes[2], None);
+        assert_eq!(readings.axes[3], Some(-12.5));
+        assert_eq!(readings.dopamine, Some(0.8));
+
+        let mut position = DimensionalPosition {
+            emergence: 1.0,
+            coherence: 7.0,
+            resilience: 3.0,
+            intelligence: 4.0,
+            efficiency: 5.0,
+            integration: 6.0,
+        };
+        readings.apply_to(&mut position);
+        assert_eq!(position.emergence, 42.0);
+        assert_eq!(position.coherence, 7.0);
+        assert_eq!(position.intelligence, -12.5);
+    }
+
+    #[test]
+    fn test_dynamics_magnitudes_are_validated() {
+        let response = "ENERGY_DYNAMICS:\nSHIFT_VECTOR: up\nMAGNITUDE: -35 (falling)\n\nDOPAMINE_DYNAMICS:\nMAGNITUDE: high\n";
+        let readings = DimensionReadings::parse_dynamics(response);
+        assert_eq!(readings.energy, Some(-35.0));
+        assert_eq!(readings.dopamine, None);
+
+        let issues = validate_dimensional_analysis(response);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("DOPAMINE_DYNAMICS"));
+        assert!(validate_dimensional_analysis(&response.replace("high", "0.6")).is_empty());
+    }
+
+    #[test]
+    fn test_validate_thought_batch_reports_missing_fields() {
+        let complete = Uuid::new_v4();
+        let partial = Uuid::new_v4();
+        let missing = Uuid::new_v4();
+        let response = format!(
+            "### CELL {}\nTHOUGHT: a\nRELEVANCE: 0.7\nDIMENSIONS:\n- EMERGENT_INTELLIGENCE: 1\n- RESOURCE_EFFICIENCY: 2\n- NETWORK_COHERENCE: 3\n- GOAL_ALIGNMENT: 4\n- TEMPORAL_RESILIENCE: 5\n- DIMENSIONAL_INTEGRATION: 6\nDOPAMINE: 0.5\n\n### CELL {}\nTHOUGHT: b\nRELEVANCE: 0.4\n",
+            complete, partial
+        );
+
+        let response = response.replacen("THOUGHT: a\n", &format!("THOUGHT: a\nREFERENCES: {}/t-1\n", partial), 1);
+        let issues = validate_thought_batch(&response, &[complete, partial, missing]);
+        assert!(issues.iter().all(|i| !i.contains(&complete.to_string())));
+        assert_eq!(issues.iter().filter(|i| i.contains(&partial.to_string())).count(), 2);
+        assert!(issues.iter().any(|i| i.contains(&missing.to_string()) && i.contains("missing")));
+    }
+
+    #[test]
+    fn test_validate_plan_requires_named_component() {
+        assert!(validate_plan("COMPONENT: Memory Router\nMETRICS: latency").is_empty());
+        assert!(!validate_plan("COMPONENT: [Name]\nSUMMARY: nothing").is_empty());
+    }
+}
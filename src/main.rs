@@ -19,7 +19,11 @@ use std::sync::{Arc, Mutex};
 use clap::{App, Arg};
 use crate::models::types::Coordinates;
 use crate::models::constants::{BATCH_SIZE, CELL_INIT_DELAY_MS, CYCLE_DELAY_MS};
-use crate::systems::colony::Colony;
+use crate::systems::colony::{Colony, ReproductionMode};
+use crate::systems::lenia::{LeniaParams, LeniaWorld, PRESETS};
+use crate::systems::movement::MovementRules;
+use crate::systems::execution::PlanEvaluator;
+use crate::systems::ltl::LtlRules;
 use rand::Rng;
 use std::time::Duration;
 use tokio::time;
@@ -31,7 +35,9 @@ use serde_json::json;
 use tokio::sync::mpsc::{self, Sender};
 
 use crate::utils::animations::{AnimationStyle, AnimationConfig, ThinkingAnimation};
-use crate::api::{ModelClient, LocalLLMClient, OpenRouterClient};
+use crate::api::{EmbeddingClient, ModelClient, LocalLLMClient, OpenRouterClient, CachedClient, ResponseCache};
+use crate::api::embeddings::{LocalEmbeddingClient, OpenAIEmbeddingClient};
+use crate::models::constants::{RESPONSE_CACHE_DIR, RESPONSE_CACHE_TTL_SECS};
 
 const DEFAULT_INITIAL_CELLS: usize = 32;
 
@@ -137,6 +143,74 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .value_name("COUNT")
             .help("Sets the initial number of cells (default: 32)")
             .takes_value(true))
+        .arg(Arg::with_name("prompts")
+            .short('p')
+            .long("prompts")
+            .value_name("DIR")
+            .help("Load prompt templates from a directory (default: prompts)")
+            .takes_value(true))
+        .arg(
+            Arg::with_name("cache")
+            .long("cache")
+            .help("Cache LLM responses on disk and reuse them for repeated prompts")
+            .takes_value(false)
+        )
+        .arg(Arg::with_name("cache-ttl")
+            .long("cache-ttl")
+            .value_name("SECS")
+            .help("Seconds before a cached response expires (default: 21600)")
+            .takes_value(true))
+        .arg(Arg::with_name("embeddings")
+            .long("embeddings")
+            .value_name("PROVIDER")
+            .help("Embed thoughts for content-aware plan building: openai or local")
+            .possible_values(["openai", "local"])
+            .takes_value(true))
+        .arg(Arg::with_name("capacity")
+            .long("capacity")
+            .value_name("CELLS")
+            .help("Carrying capacity: the population at which resources stop regrowing (default: 100)")
+            .takes_value(true))
+        .arg(Arg::with_name("reproduction")
+            .long("reproduction")
+            .value_name("MODE")
+            .help("How cells reproduce: budding, crossover with a complementary neighbour, or mixed (default)")
+            .possible_values(["budding", "crossover", "mixed"])
+            .takes_value(true))
+        .arg(Arg::with_name("llm-merge")
+            .long("llm-merge")
+            .help("Merge both parents' memories with the model when cells cross over")
+            .takes_value(false))
+        .arg(Arg::with_name("ltl-rules")
+            .long("ltl-rules")
+            .value_name("RULES")
+            .help("Larger-than-Life birth/survival neighbour counts, e.g. B1-8/S1-12")
+            .takes_value(true))
+        .arg(Arg::with_name("plan-evaluator")
+            .long("plan-evaluator")
+            .value_name("EVALUATOR")
+            .help("How finished plan steps are judged: rules (default) or llm")
+            .possible_values(["rules", "llm"])
+            .takes_value(true))
+        .arg(Arg::with_name("no-consensus")
+            .long("no-consensus")
+            .help("Let each group of cells keep its own plan instead of merging similar plans and voting")
+            .takes_value(false))
+        .arg(Arg::with_name("movement")
+            .long("movement")
+            .value_name("FILE")
+            .help("JSON file of movement rules: complement, field, energy and crowding weights, sense radius, speed")
+            .takes_value(true))
+        .arg(Arg::with_name("max-speed")
+            .long("max-speed")
+            .value_name("UNITS")
+            .help("Furthest a cell can move in one cycle; 0 keeps cells in place (default: 0.5)")
+            .takes_value(true))
+        .arg(Arg::with_name("lenia")
+            .long("lenia")
+            .value_name("PRESET|FILE")
+            .help("Lenia field to run under the colony: a preset name or a JSON parameter file")
+            .takes_value(true))
         .arg(
             Arg::with_name("local-model")
             .long("local-model")
@@ -154,16 +228,94 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .to_string();
         
     let colony_name = matches.value_of("name").unwrap_or("Unnamed");
+
+    if let Some(prompts_dir) = matches.value_of("prompts") {
+        match crate::api::prompts::load_prompt_dir(prompts_dir) {
+            Ok(count) => println!("Loaded {} prompt templates from {}", count, prompts_dir),
+            Err(e) => eprintln!("Error loading prompt templates from {}: {}", prompts_dir, e)
+        }
+    }
     
 
-    let api_client: Box<dyn ModelClient> = if matches.is_present("local-model") {
-        Box::new(LocalLLMClient::new()?)
+    let response_cache = if matches.is_present("cache") || matches.is_present("cache-ttl") {
+        let ttl_secs = matches.value_of("cache-ttl")
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(RESPONSE_CACHE_TTL_SECS);
+        Some(ResponseCache::new(RESPONSE_CACHE_DIR, Duration::from_secs(ttl_secs))?)
+    } else {
+        None
+    };
+
+    let (api_client, model_name): (Box<dyn ModelClient>, &str) = if matches.is_present("local-model") {
+        (Box::new(LocalLLMClient::new()?), "local")
     } else {
         let api_key = std::env::var("OPENROUTER_API_KEY")
             .map_err(|_| "OPENROUTER_API_KEY not set")?;
-        Box::new(OpenRouterClient::new(api_key)?)
+        (Box::new(OpenRouterClient::new(api_key)?), "openrouter")
     };
+    let api_client: Box<dyn ModelClient> = match response_cache {
+        Some(cache) => Box::new(CachedClient::new(api_client, model_name, cache)),
+        None => api_client,
+    };
+    let embedding_client: Option<Box<dyn EmbeddingClient>> = match matches.value_of("embeddings") {
+        Some("local") => Some(Box::new(LocalEmbeddingClient::new())),
+        Some(_) => {
+            let api_key = std::env::var("EMBEDDING_API_KEY")
+                .or_else(|_| std::env::var("OPENAI_API_KEY"))
+                .ok();
+            let mut client = OpenAIEmbeddingClient::new(api_key);
+            if let Ok(base_url) = std::env::var("EMBEDDING_BASE_URL") {
+                client = client.with_base_url(&base_url);
+            }
+            if let Ok(model) = std::env::var("EMBEDDING_MODEL") {
+                client = client.with_model(&model);
+            }
+            Some(Box::new(client))
+        }
+        None => None,
+    };
+
     let mut colony = Colony::new(&mission, api_client);
+    if let Some(embedding_client) = embedding_client {
+        println!("Embedding thoughts with {}", embedding_client.model_name());
+        colony = colony.with_embedding_client(embedding_client);
+    }
+    if let Some(capacity) = matches.value_of("capacity") {
+        colony = colony.with_carrying_capacity(capacity.parse()?);
+    }
+    if matches.is_present("reproduction") || matches.is_present("llm-merge") {
+        let mode = matches.value_of("reproduction").unwrap_or("mixed").parse::<ReproductionMode>()?;
+        colony = colony.with_reproduction(mode, matches.is_present("llm-merge"));
+    }
+    if let Some(rules) = matches.value_of("ltl-rules") {
+        colony = colony.with_ltl_rules(rules.parse::<LtlRules>()?);
+    }
+    if let Some(evaluator) = matches.value_of("plan-evaluator") {
+        colony = colony.with_plan_evaluator(evaluator.parse::<PlanEvaluator>()?);
+    }
+    if matches.is_present("no-consensus") {
+        colony = colony.with_consensus(false);
+    }
+    if matches.is_present("movement") || matches.is_present("max-speed") {
+        let mut movement = match matches.value_of("movement") {
+            Some(path) => MovementRules::from_file(std::path::Path::new(path))?,
+            None => MovementRules::default(),
+        };
+        if let Some(max_speed) = matches.value_of("max-speed") {
+            movement.max_speed = max_speed.parse()?;
+        }
+        colony = colony.with_movement_rules(movement);
+    }
+    if let Some(lenia) = matches.value_of("lenia") {
+        let world = if let Some((params, seed)) = LeniaParams::preset(lenia) {
+            LeniaWorld::seeded(params, &seed)
+        } else if std::path::Path::new(lenia).exists() {
+            LeniaWorld::new(LeniaParams::from_file(std::path::Path::new(lenia))?)
+        } else {
+            return Err(format!("Unknown Lenia preset '{}' (available: {})", lenia, PRESETS.join(", ")).into());
+        };
+        colony = colony.with_lenia_world(world);
+    }
 
     let state_file = matches.value_of("state").unwrap_or("eca_state.json");
     if std::path::Path::new(state_file).exists() {
@@ -175,7 +327,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         loading_animation.run().await?;
         
         match colony.load_state_from_file(state_file) {
-            Ok(_) => println!("Loaded colony state from {}", state_file),
+            Ok(_) => println!("Loaded colony state from {} ({} cells)", state_file, colony.cells.len()),
             Err(e) => eprintln!("Error loading state from {}: {}", state_file, e)
         }
     } else {
@@ -184,6 +336,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // A restored colony is only topped up to the initial size
+    let initial_cells = initial_cells.saturating_sub(colony.cells.len());
     let colony = Arc::new(Mutex::new(colony));
     let colony_ws = Arc::clone(&colony);
 
@@ -368,6 +522,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 
             println!("╚══════════════════════════════════════════════════════════╝");
         }
+
+        if let Err(e) = colony.lock().unwrap().reach_consensus(&current_cycle.to_string()).await {
+            eprintln!("Error reaching plan consensus: {}", e);
+        }
+
+        if let Err(e) = colony.lock().unwrap().execute_plans(&current_cycle.to_string()).await {
+            eprintln!("Error executing plans: {}", e);
+        }
         
         // Evolution phase
         let evolution_animation = ThinkingAnimation::new(AnimationConfig {
@@ -11,6 +11,7 @@ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLI
 pub const MAX_MEMORY_SIZE: usize = 50000;
 pub const MAX_THOUGHTS_FOR_PLAN: usize = 42;
 pub const NEIGHBOR_DISTANCE_THRESHOLD: f64 = 2.0;
+pub const SPATIAL_BUCKET_SIZE: f64 = NEIGHBOR_DISTANCE_THRESHOLD; // Grid spacing of the colony's spatial index
 pub const BATCH_SIZE: usize = 5;
 
 // Timing constants
@@ -18,9 +19,94 @@ pub const CELL_INIT_DELAY_MS: u64 = 2;
 pub const CYCLE_DELAY_MS: u64 = 10;
 pub const API_TIMEOUT_SECS: u64 = 300;
 
+// Response cache constants
+pub const RESPONSE_CACHE_DIR: &str = "data/cache";
+pub const RESPONSE_CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+// Response validation constants
+pub const MAX_REPAIR_ATTEMPTS: usize = 2;
+pub const MAX_REPAIR_RESPONSE_CHARS: usize = 16000;
+
+// Embedding constants
+pub const DEFAULT_EMBEDDING_BASE_URL: &str = "https://api.openai.com/v1";
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+pub const PLAN_THOUGHT_RELEVANCE_WEIGHT: f64 = 0.7; // 1.0 ignores diversity when picking plan thoughts
+
+// Novelty constants
+pub const DUPLICATE_THOUGHT_SIMILARITY: f64 = 0.85;
+pub const RECENT_THOUGHTS_PER_CELL: usize = 10;
+pub const NOVELTY_DOPAMINE_GAIN: f64 = 0.2;
+pub const NOVELTY_ENERGY_GAIN: f64 = 10.0;
+
 // API constants
 pub const MAX_TOKENS_GROK: usize = 120000;
 pub const MAX_TOKENS_CLAUDE: usize = 8096;
 pub const MAX_TOKENS_GEMINI: usize = 8096;
 pub const MAX_PROMPT_TOKENS: usize = 6072; // Reserve 1024 for response
 pub const TOKEN_PADDING: usize = 50; // Safety margin
+
+// Lenia field constants
+pub const LENIA_GRID_SIZE: usize = 32;
+pub const LENIA_KERNEL_RADIUS: f64 = 4.0;
+pub const LENIA_STEPS_PER_CYCLE: usize = 1;
+pub const LENIA_DEPOSIT_RADIUS: usize = 1;
+pub const LENIA_DEPOSIT_STRENGTH: f64 = 0.3;
+pub const LENIA_DIMENSION_COUPLING: f64 = 0.05; // Pull toward the neighbours' mean on each axis per cycle
+pub const LENIA_DIMENSION_MEMORY: f64 = 0.5; // Share of earlier dimension deposits kept each cycle
+pub const LENIA_BOUNDS_MARGIN: f64 = 2.0; // World units kept around the outermost cells when the field grows
+
+// LTL neighbourhood rules
+pub const LTL_RULES: &str = "B1-8/S1-12"; // Birth/survival neighbour-count ranges
+pub const LTL_SURVIVAL_PENALTY: f64 = 5.0; // Energy lost per tick outside the survival range
+pub const LTL_OFFSPRING_ENERGY_SHARE: f64 = 0.3; // Share of the parent's energy handed to a bud
+pub const LTL_TRANSFER_GRADIENT: f64 = 0.5; // Neighbour energy lead (as a fraction of 100) that triggers a transfer
+
+// Metabolism and population
+pub const METABOLIC_COST_PER_CYCLE: f64 = 2.0; // Energy an awake cell burns every cycle
+pub const ENERGY_COST_PER_LLM_CALL: f64 = 1.5;
+pub const ENERGY_COST_PER_THOUGHT: f64 = 1.0; // Charged for each thought actually stored
+pub const RESOURCE_REGEN_PER_CYCLE: f64 = 10.0; // Energy per cell per cycle in an empty colony, shrinking to zero at capacity
+pub const CARRYING_CAPACITY: usize = 100;
+pub const DORMANCY_WAKE_ENERGY: f64 = 20.0; // A dormant cell wakes once it has regained this much
+pub const DORMANCY_MAX_CYCLES: u32 = 5; // Cycles a cell can stay dormant before it dies
+pub const CELL_ARCHIVE_DIR: &str = "data/archive";
+pub const LINEAGE_DIR: &str = "data/lineage"; // Newick and JSON family trees, rewritten on every save
+
+// Inheritance
+pub const DNA_MUTATION_SPREAD: f64 = 10.0; // Largest change to a child's dimension at a mutation rate of 1.0
+pub const MUTATION_RATE_DRIFT: f64 = 0.05; // Largest change to the mutation rate itself between generations
+pub const INHERITED_MEMORIES: usize = 3; // Most recent compressed memories passed to a child
+pub const INHERITED_RESEARCH_TOPICS: usize = 5;
+pub const CROSSOVER_MIN_COMPLEMENT: f64 = 0.7; // Dimensional complement two neighbours need to cross over
+pub const CROSSOVER_MIN_ENERGY: f64 = 60.0; // Energy the partner needs to take part in a crossover
+
+// Messaging
+pub const MESSAGE_INBOX_CAPACITY: usize = 8; // Messages a cell can hold before the oldest are dropped
+pub const COLLABORATION_HISTORY_LEN: usize = 20; // Exchanges a cell remembers
+pub const COLLABORATIONS_IN_CONTEXT: usize = 5;
+
+// Plan execution
+pub const PLAN_PROGRESS_PER_CYCLE: f64 = 0.5; // Progress a ready step makes per cycle with fully energised participants
+pub const PLAN_STEP_ENERGY_REWARD: f64 = 3.0; // Per participant, for each step that passes review
+pub const PLAN_STEP_DOPAMINE_REWARD: f64 = 0.05;
+pub const PLAN_COMPLETION_ENERGY_REWARD: f64 = 20.0; // Per participant, when the whole plan completes
+pub const PLAN_COMPLETION_DOPAMINE_REWARD: f64 = 0.2;
+pub const PLAN_FAILURE_DOPAMINE_PENALTY: f64 = 0.1;
+
+// Consensus
+pub const PLAN_MERGE_SHINGLE_SIMILARITY: f64 = 0.3; // Word-shingle overlap at which two proposals are merged
+pub const PLAN_MERGE_EMBEDDING_SIMILARITY: f64 = 0.85; // Embedding similarity at which two proposals are merged
+pub const PLAN_MERGE_MAX_THOUGHTS: usize = 12; // Most relevant thoughts a merged plan keeps
+pub const CONSENSUS_QUORUM: f64 = 0.5; // Vote share a plan needs to become the colony's master plan
+
+// Movement
+pub const MOVEMENT_COMPLEMENT_WEIGHT: f64 = 0.3; // Pull toward neighbours with complementary Thought DNA
+pub const MOVEMENT_FIELD_WEIGHT: f64 = 2.0; // Pull up the Lenia activity gradient
+pub const MOVEMENT_ENERGY_WEIGHT: f64 = 0.1; // Pull toward energetic neighbours
+pub const MOVEMENT_CROWDING_WEIGHT: f64 = 0.6; // Push away from neighbours once the area is crowded
+pub const MOVEMENT_COMFORT_NEIGHBORS: usize = 6; // Neighbours in sensing range before crowding kicks in
+pub const MOVEMENT_SENSE_RADIUS: f64 = 4.0;
+pub const MOVEMENT_MAX_SPEED: f64 = 0.5; // World units per cycle
+pub const MOVEMENT_ENERGY_COST: f64 = 1.0; // Energy per world unit travelled
+pub const MOVEMENT_WORLD_MIN: f64 = -32.0; // Lower wall of the world cube on every axis
+pub const MOVEMENT_WORLD_MAX: f64 = 32.0; // Upper wall of the world cube on every axis
@@ -12,6 +12,7 @@ pub mod types;
 pub mod constants;
 pub mod knowledge;
 pub mod plan_analysis;
+pub mod plan_graph;
 pub mod state;
 
 pub use types::*;
@@ -9,6 +9,7 @@ The above copyright notice and this permission notice shall be included in all c
 THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
 
 use crate::models::types::{Plan, PlanStatus};
+use crate::models::plan_graph::{to_dot, to_mermaid};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -26,6 +27,10 @@ pub struct PlanAnalysis {
     pub best_plan_id: Option<Uuid>,
     pub best_plan_score: f64,
     pub best_plan_summary: String,
+    #[serde(default)]
+    pub completed_overall: u64, // Plans completed since the colony started
+    #[serde(default)]
+    pub failed_overall: u64, // Plans failed since the colony started
 }
 
 impl PlanAnalysis {
@@ -57,9 +62,17 @@ impl PlanAnalysis {
             best_plan_id: best_plan.map(|p| p.id),
             best_plan_score: best_plan.map(|p| p.score).unwrap_or(0.0),
             best_plan_summary: best_plan.map(|p| p.summary.clone()).unwrap_or_default(),
+            completed_overall: 0,
+            failed_overall: 0,
         }
     }
 
+    pub fn with_overall(mut self, completed: u64, failed: u64) -> Self {
+        self.completed_overall = completed;
+        self.failed_overall = failed;
+        self
+    }
+
     pub fn save_to_file(&self, base_path: &Path) -> std::io::Result<()> {
         let analysis_dir = base_path.join("analysis");
         fs::create_dir_all(&analysis_dir)?;
@@ -83,6 +96,8 @@ pub fn save_plan_to_file(plan: &Plan, base_path: &Path, cycle_id: &str) -> std::
     
     let json = serde_json::to_string_pretty(plan)?;
     fs::write(file_path, json)?;
+    fs::write(plans_dir.join(format!("plan_{}.dot", plan.id)), to_dot(plan))?;
+    fs::write(plans_dir.join(format!("plan_{}.mmd", plan.id)), to_mermaid(plan))?;
     
     Ok(())
 }
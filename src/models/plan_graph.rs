@@ -0,0 +1,334 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::types::{Plan, PlanNode, PlanNodeStatus, PlanStatus, Thought};
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// The value of a `KEY: value` line, tolerating markdown emphasis and numbering
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line
+        .trim_start_matches(|c: char| c == '#' || c == '*' || c == '-' || c == '.' || c.is_ascii_digit() || c.is_whitespace())
+        .strip_prefix(key)?
+        .trim_start_matches('*')
+        .strip_prefix(':')?;
+    Some(rest.trim().trim_matches('*').trim().trim_matches(|c| c == '[' || c == ']').trim())
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+fn new_node(title: String, description: String) -> PlanNode {
+    PlanNode {
+        id: Uuid::new_v4(),
+        title,
+        description,
+        dependencies: Vec::new(),
+        estimated_completion: 0.0,
+        progress: 0.0,
+        status: PlanNodeStatus::Pending,
+    }
+}
+
+/// Builds one node per `COMPONENT:` block. The block's other lines become its
+/// description and its `DEPENDS_ON:` names become edges to the components with
+/// those names; unknown names are ignored. Fails if the edges form a cycle.
+pub fn parse_plan_nodes(text: &str) -> Result<Vec<PlanNode>, String> {
+    let mut blocks: Vec<(String, Vec<String>, Vec<String>)> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(name) = field(line, "COMPONENT") {
+            if !name.is_empty() && name != "Name" {
+                blocks.push((name.to_string(), Vec::new(), Vec::new()));
+            }
+        } else if let Some(block) = blocks.last_mut() {
+            if let Some(depends_on) = field(line, "DEPENDS_ON") {
+                block.2.extend(depends_on.split(',').map(normalize).filter(|name| !name.is_empty() && name != "none"));
+            } else if !line.is_empty() {
+                block.1.push(line.to_string());
+            }
+        }
+    }
+
+    let mut nodes: Vec<PlanNode> = blocks.iter()
+        .map(|(title, lines, _)| new_node(title.clone(), lines.join("\n")))
+        .collect();
+    let ids: HashMap<String, Uuid> = nodes.iter().rev().map(|node| (normalize(&node.title), node.id)).collect();
+    for (node, (_, _, depends_on)) in nodes.iter_mut().zip(&blocks) {
+        for name in depends_on {
+            if let Some(&id) = ids.get(name) {
+                if id != node.id && !node.dependencies.contains(&id) {
+                    node.dependencies.push(id);
+                }
+            }
+        }
+    }
+
+    match find_cycle(&nodes) {
+        Some(cycle) => Err(format!("Plan dependencies form a cycle: {}", cycle.join(" -> "))),
+        None => Ok(nodes),
+    }
+}
+
+/// A proposed plan from a `SUMMARY:` paragraph and its `COMPONENT:` blocks.
+/// A response without components yields a plan with no steps, which fails
+/// once executed. The colony scores plans, so the score starts at zero.
+pub fn parse_plan(text: &str, thoughts: &[Thought]) -> Result<Plan, String> {
+    let nodes = parse_plan_nodes(text)?;
+    let mut summary = String::new();
+    let mut in_summary = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("SUMMARY:") {
+            in_summary = true;
+            summary = line.trim_start_matches("SUMMARY:").trim().to_string();
+        } else if line.starts_with("COMPONENTS:") ||
+                  line.starts_with("COMPONENT:") ||
+                  line.starts_with("IMPLEMENTATION:") ||
+                  line.starts_with("TECHNICAL COMPONENTS:") {
+            in_summary = false;
+        } else if in_summary {
+            summary.push(' ');
+            summary.push_str(line);
+        }
+    }
+
+    Ok(Plan {
+        id: Uuid::new_v4(),
+        thoughts: thoughts.to_vec(),
+        nodes,
+        summary,
+        score: 0.0,
+        participating_cells: Vec::new(),
+        created_at: Utc::now(),
+        status: PlanStatus::Proposed,
+    })
+}
+
+/// Nodes for a numbered list of steps, each depending on the one before.
+pub fn parse_numbered_steps(text: &str) -> Vec<PlanNode> {
+    let mut nodes: Vec<PlanNode> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        let Some((number, step)) = line.split_once('.') else { continue };
+        if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) || step.trim().is_empty() {
+            continue;
+        }
+        let mut node = new_node(step.trim().to_string(), String::new());
+        if let Some(previous) = nodes.last() {
+            node.dependencies.push(previous.id);
+        }
+        nodes.push(node);
+    }
+    nodes
+}
+
+/// Titles along a dependency cycle, first title repeated at the end.
+pub fn find_cycle(nodes: &[PlanNode]) -> Option<Vec<String>> {
+    let index: HashMap<Uuid, usize> = nodes.iter().enumerate().map(|(i, node)| (node.id, i)).collect();
+    // 0 = unvisited, 1 = on the current path, 2 = finished
+    let mut state = vec![0u8; nodes.len()];
+
+    for start in 0..nodes.len() {
+        if state[start] != 0 {
+            continue;
+        }
+        state[start] = 1;
+        let mut stack = vec![(start, 0usize)];
+        while let Some((node, next)) = stack.last().copied() {
+            let Some(dependency) = nodes[node].dependencies.get(next) else {
+                state[node] = 2;
+                stack.pop();
+                continue;
+            };
+            if let Some(top) = stack.last_mut() {
+                top.1 += 1;
+            }
+            let Some(&dep) = index.get(dependency) else { continue };
+            match state[dep] {
+                0 => {
+                    state[dep] = 1;
+                    stack.push((dep, 0));
+                }
+                1 => {
+                    let from = stack.iter().position(|(n, _)| *n == dep).unwrap_or(0);
+                    let mut cycle: Vec<String> = stack[from..].iter().map(|(n, _)| nodes[*n].title.clone()).collect();
+                    cycle.push(nodes[dep].title.clone());
+                    return Some(cycle);
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// A plan written back in the `COMPONENT:` format it was parsed from.
+pub fn to_components(plan: &Plan) -> String {
+    let titles: HashMap<Uuid, &str> = plan.nodes.iter().map(|node| (node.id, node.title.as_str())).collect();
+    let mut text = format!("SUMMARY: {}\n", plan.summary);
+    for node in &plan.nodes {
+        let depends_on: Vec<&str> = node.dependencies.iter().filter_map(|id| titles.get(id).copied()).collect();
+        text.push_str(&format!("COMPONENT: {}\n", node.title));
+        if !node.description.is_empty() {
+            text.push_str(&node.description);
+            text.push('\n');
+        }
+        text.push_str(&format!("DEPENDS_ON: {}\n", if depends_on.is_empty() { "none".to_string() } else { depends_on.join(", ") }));
+    }
+    text
+}
+
+fn status_class(status: &PlanNodeStatus) -> &'static str {
+    match status {
+        PlanNodeStatus::Pending => "pending",
+        PlanNodeStatus::InProgress => "in_progress",
+        PlanNodeStatus::Completed => "completed",
+        PlanNodeStatus::Blocked => "blocked",
+        PlanNodeStatus::Failed => "failed",
+    }
+}
+
+const STATUS_COLORS: [(&str, &str); 5] = [
+    ("pending", "#dddddd"),
+    ("in_progress", "#9ecae1"),
+    ("completed", "#a1d99b"),
+    ("blocked", "#fdae6b"),
+    ("failed", "#fc9272"),
+];
+
+fn color(status: &PlanNodeStatus) -> &'static str {
+    let class = status_class(status);
+    STATUS_COLORS.iter().find(|(name, _)| *name == class).map_or("#ffffff", |(_, color)| color)
+}
+
+// Edges as (dependency index, dependent index)
+fn edges(plan: &Plan) -> Vec<(usize, usize)> {
+    let index: HashMap<Uuid, usize> = plan.nodes.iter().enumerate().map(|(i, node)| (node.id, i)).collect();
+    plan.nodes.iter()
+        .enumerate()
+        .flat_map(|(i, node)| node.dependencies.iter().filter_map(|dep| index.get(dep)).map(move |&d| (d, i)))
+        .collect()
+}
+
+// A DOT string body: backslashes first so the quote escapes stay intact
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\r', "").replace('\n', "\\n")
+}
+
+/// Graphviz DOT with nodes coloured by status and edges from each
+/// dependency to the step that needs it.
+pub fn to_dot(plan: &Plan) -> String {
+    let mut dot = format!("digraph \"plan_{}\" {{\n    rankdir=LR;\n    node [shape=box, style=\"rounded,filled\"];\n", plan.id);
+    for (i, node) in plan.nodes.iter().enumerate() {
+        dot.push_str(&format!("    n{} [label=\"{}\", fillcolor=\"{}\"];\n", i, dot_escape(&node.title), color(&node.status)));
+    }
+    for (from, to) in edges(plan) {
+        dot.push_str(&format!("    n{} -> n{};\n", from, to));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Mermaid flowchart with the same layout and colours as `to_dot`.
+pub fn to_mermaid(plan: &Plan) -> String {
+    let mut mermaid = String::from("flowchart LR\n");
+    for (i, node) in plan.nodes.iter().enumerate() {
+        mermaid.push_str(&format!("    n{}[\"{}\"]:::{}\n", i, node.title.replace('"', "#quot;").replace('\n', "<br/>"), status_class(&node.status)));
+    }
+    for (from, to) in edges(plan) {
+        mermaid.push_str(&format!("    n{} --> n{}\n", from, to));
+    }
+    for (class, color) in STATUS_COLORS {
+        mermaid.push_str(&format!("    classDef {} fill:{}\n", class, color));
+    }
+    mermaid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::execution::settle;
+
+    const PLAN: &str = "SUMMARY: Build it\n\
+        **COMPONENT:** Data Layer\n\
+        TECHNICAL_BASELINE: flat files\n\
+        DEPENDS_ON: none\n\
+        COMPONENT: [Query API]\n\
+        ENHANCEMENT_VECTOR: add an index\n\
+        DEPENDS_ON: Data layer, Unknown Thing\n\
+        3. COMPONENT: Dashboard\n\
+        DEPENDS_ON: [Query API, Data Layer]\n";
+
+    #[test]
+    fn test_parses_components_and_edges() {
+        let nodes = parse_plan_nodes(PLAN).unwrap();
+        let titles: Vec<&str> = nodes.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["Data Layer", "Query API", "Dashboard"]);
+        assert_eq!(nodes[0].description, "TECHNICAL_BASELINE: flat files");
+        assert_eq!(nodes[1].dependencies, vec![nodes[0].id]);
+        assert_eq!(nodes[2].dependencies, vec![nodes[1].id, nodes[0].id]);
+
+        let plan = Plan {
+            id: Uuid::new_v4(),
+            thoughts: Vec::new(),
+            nodes,
+            summary: String::new(),
+            score: 0.0,
+            participating_cells: Vec::new(),
+            created_at: Utc::now(),
+            status: PlanStatus::Proposed,
+        };
+        assert!(to_dot(&plan).contains("n0 -> n2;"));
+        assert!(to_mermaid(&plan).contains("n1 --> n2"));
+
+        let reparsed = parse_plan_nodes(&to_components(&plan)).unwrap();
+        assert_eq!(reparsed.len(), 3);
+        assert_eq!(reparsed[2].dependencies, vec![reparsed[1].id, reparsed[0].id]);
+    }
+
+    #[test]
+    fn test_dot_labels_escape_backslashes_quotes_and_newlines() {
+        let mut plan = parse_plan("COMPONENT: Placeholder\n", &[]).unwrap();
+        plan.nodes[0].title = "Read C:\\data\\ \"raw\"\nthen parse\\".to_string();
+        let dot = to_dot(&plan);
+        assert!(dot.contains(r#"label="Read C:\\data\\ \"raw\"\nthen parse\\", fillcolor"#), "{}", dot);
+        assert_eq!(dot.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_rejects_cycles() {
+        let cyclic = "COMPONENT: A\nDEPENDS_ON: C\nCOMPONENT: B\nDEPENDS_ON: A\nCOMPONENT: C\nDEPENDS_ON: B\n";
+        let error = parse_plan_nodes(cyclic).unwrap_err();
+        assert!(error.contains("A -> C -> B -> A"), "{}", error);
+    }
+
+    #[test]
+    fn test_plans_without_components_fail() {
+        let mut plan = parse_plan("SUMMARY: Improve things\n- Analysis: look around\n", &[]).unwrap();
+        assert_eq!(plan.summary, "Improve things - Analysis: look around");
+        assert!(plan.nodes.is_empty());
+        settle(&mut plan);
+        assert!(matches!(plan.status, PlanStatus::Failed));
+    }
+
+    #[test]
+    fn test_numbered_steps_form_a_chain() {
+        let nodes = parse_numbered_steps("SUMMARY: x\nSTEPS:\n1. Gather data\n2. Train model\nSCORE: 0.8");
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].dependencies, vec![nodes[0].id]);
+    }
+}
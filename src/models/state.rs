@@ -15,6 +15,7 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::collections::HashMap;
 use crate::models::types::{Plan, Thought, DimensionalPosition};
+use crate::systems::lineage::Lineage;
 
 #[derive(Serialize, Deserialize)]
 pub struct CellState {
@@ -33,6 +34,32 @@ pub struct CellState {
     pub x: f64,
     pub y: f64, 
     pub z: f64,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub co_parent_id: Option<Uuid>,
+}
+
+/// What is kept of a cell after it dies.
+#[derive(Serialize, Deserialize)]
+pub struct CellArchive {
+    pub id: Uuid,
+    pub died_at: DateTime<Utc>,
+    pub energy_spent: f64,
+    pub thoughts: Vec<Thought>,
+    pub compressed_memories: Vec<String>,
+    pub current_plan: Option<Plan>,
+    pub research_topics: Vec<String>,
+    pub dimensional_position: DimensionalPosition,
+}
+
+impl CellArchive {
+    pub fn save_to_dir(&self, dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(dir.join(format!("cell_{}.json", self.id)), json)?;
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -43,11 +70,15 @@ pub struct ColonyState {
     pub mission: String,
     pub lenia_world: Option<LeniaWorldState>,
     pub energy_grid: EnergyGridState,
+    #[serde(default)]
+    pub lineage: Lineage,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct EnergyGridState {
     pub size: usize,
+    #[serde(default)]
+    pub origin: [f64; 3], // World position of grid index (0, 0, 0)
     pub grid: Vec<f64>,  // Flattened 3D array of energy values
     pub cell_positions: HashMap<Uuid, (usize, usize, usize)>, // Maps cell IDs to grid coordinates
 }
@@ -58,6 +89,10 @@ pub struct LeniaWorldState {
     pub size: usize,
     pub growth_mu: f64,
     pub growth_sigma: f64,
+    #[serde(default)]
+    pub channels: usize, // grid holds this many size^3 channels back to back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[usize; 3]>, // set for non-cubic grids, replacing size^3
 }
 
 impl ColonyState {
@@ -59,6 +59,8 @@ pub struct CellContext {
     pub energy_level: f64,
     pub dimensional_position: DimensionalPosition,
     pub dopamine: f64,
+    #[serde(default)]
+    pub inbox: Vec<String>,         // Messages from other cells since the last thought
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -80,6 +82,24 @@ pub struct Coordinates {
     pub integration_score: f64,
 }
 
+impl DimensionalPosition {
+    /// Axis values in declaration order.
+    pub fn axes(&self) -> [f64; 6] {
+        [self.emergence, self.coherence, self.resilience, self.intelligence, self.efficiency, self.integration]
+    }
+
+    pub fn axes_mut(&mut self) -> [&mut f64; 6] {
+        [
+            &mut self.emergence,
+            &mut self.coherence,
+            &mut self.resilience,
+            &mut self.intelligence,
+            &mut self.efficiency,
+            &mut self.integration,
+        ]
+    }
+}
+
 impl Default for Coordinates {
     fn default() -> Self {
         Self {
@@ -108,6 +128,15 @@ pub struct Thought {
     pub confidence_score: f64,
     pub ascii_visualization: Option<String>,
     pub referenced_thoughts: Vec<(Uuid, String)>, // (cell_id, thought_id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    #[serde(default = "default_novelty")]
+    pub novelty_score: f64,         // 1.0 = unlike anything the colony recently thought
+}
+
+// Thoughts saved before novelty was scored count as novel rather than duplicates
+fn default_novelty() -> f64 {
+    1.0
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -117,6 +146,8 @@ pub struct PlanNode {
     pub description: String,
     pub dependencies: Vec<Uuid>,
     pub estimated_completion: f64, // 0.0 to 1.0
+    #[serde(default)]
+    pub progress: f64, // Execution progress, 0.0 to 1.0
     pub status: PlanNodeStatus,
 }
 
@@ -141,6 +172,13 @@ pub struct Plan {
     pub status: PlanStatus,
 }
 
+impl Plan {
+    /// Still waiting to start or being carried out.
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, PlanStatus::Proposed | PlanStatus::InProgress)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PlanStatus {
     Proposed,
@@ -170,4 +208,15 @@ pub struct ColonyStatistics {
     pub average_cell_energy: f64,
     pub highest_evolution_stage: u32,
     pub total_cycles: u32,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub malformed_responses: u64,
+    pub repaired_responses: u64,
+    pub duplicate_thoughts: u64,
+    pub average_novelty: f64,
+    pub phase_order: f64,
+    pub dormant_cells: u32,
+    pub deaths: u64,
+    pub energy_spent: f64,
+    pub carrying_capacity: u32,
 }
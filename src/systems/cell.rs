@@ -10,12 +10,17 @@ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLI
 
 use crate::models::types::{CellContext, Coordinates, DimensionalPosition, Plan, RealTimeContext, Thought};
 use crate::models::thought_io::{EventInput, EventOutput, ThoughtIO};
-use crate::models::constants::MAX_MEMORY_SIZE;
-use crate::api::openrouter::OpenRouterClient;
-use crate::systems::ltl::{ExtendedNeighborhood, EnhancedCellState, InteractionEffect};
+use crate::models::constants::{MAX_MEMORY_SIZE, DUPLICATE_THOUGHT_SIMILARITY, NOVELTY_DOPAMINE_GAIN, NOVELTY_ENERGY_GAIN};
+use crate::models::constants::{LTL_SURVIVAL_PENALTY, LTL_TRANSFER_GRADIENT, ENERGY_COST_PER_LLM_CALL, ENERGY_COST_PER_THOUGHT};
+use crate::models::constants::{COLLABORATION_HISTORY_LEN, COLLABORATIONS_IN_CONTEXT};
+use crate::models::constants::{DNA_MUTATION_SPREAD, MUTATION_RATE_DRIFT, INHERITED_MEMORIES, INHERITED_RESEARCH_TOPICS};
+use crate::systems::ltl::{ExtendedNeighborhood, EnhancedCellState, InteractionEffect, LtlRules};
+use crate::systems::messaging::{parse_references, Collaboration, Message, MessageKind};
+use crate::systems::novelty::{closest_thought, merge_duplicate, ClosestThought};
 use crate::api::model_client::ModelClient;  // Add this import
+use crate::api::validation::{repair_stats, DimensionReadings};
+use crate::api::embeddings::EmbeddingClient;
 use std::collections::{HashMap, VecDeque};
-use std::error::Error;
 use chrono::Utc;
 use uuid::Uuid;
 use rand::Rng;
@@ -46,6 +51,14 @@ pub struct Cell {
     pub context_influence: f64, // How much real-time context affects this cell
     pub last_context_update: Option<chrono::DateTime<chrono::Utc>>,
     pub context_alignment_score: f64,
+    pub duplicate_thoughts: usize,
+    pub energy_spent: f64,
+    // Cycles spent dormant; None while the cell is awake
+    pub dormant_cycles: Option<u32>,
+    pub parent_id: Option<Uuid>,
+    // Second parent of a crossover child
+    pub co_parent_id: Option<Uuid>,
+    pub collaborations: VecDeque<Collaboration>,
 }
 
 impl Cell {
@@ -82,96 +95,187 @@ impl Cell {
             last_context_update: None,
             context_alignment_score: 0.5,
             thought_counter: 0,
+            duplicate_thoughts: 0,
+            energy_spent: 0.0,
+            dormant_cycles: None,
+            parent_id: None,
+            co_parent_id: None,
+            collaborations: VecDeque::new(),
         }
     }
 
-    pub async fn update_with_ltl_rules(
-        &mut self, 
-        api_client: &OpenRouterClient,
-        other_cells: &[(Uuid, Coordinates)]
-    ) -> Result<(), Box<dyn Error>> {
-        self.neighborhood.update_neighbors(&self.position, other_cells);
-        let neighbor_states: HashMap<Uuid, EnhancedCellState> = self.neighborhood.neighbors.keys()
-            .filter_map(|&id| {
-                other_cells.iter()
-                    .find(|(other_id, _)| *other_id == id)
-                    .map(|_| (id, EnhancedCellState::new()))
-            })
+    /// A child at `position` carrying this cell's Thought DNA, each axis
+    /// mutated by up to `DNA_MUTATION_SPREAD` scaled by `mutation_rate`, along
+    /// with its latest compressed memories and research topics.
+    pub fn offspring(&self, position: Coordinates, rng: &mut impl Rng) -> Cell {
+        let mut child = Cell::new(position);
+        child.parent_id = Some(self.id);
+
+        child.dimensional_position = self.dimensional_position.clone();
+        let spread = DNA_MUTATION_SPREAD * self.mutation_rate;
+        for axis in child.dimensional_position.axes_mut() {
+            *axis = (*axis + rng.gen_range(-1.0..=1.0) * spread).clamp(-100.0, 100.0);
+        }
+        child.mutation_rate = (self.mutation_rate + rng.gen_range(-1.0..=1.0) * MUTATION_RATE_DRIFT).clamp(0.0, 1.0);
+        child.dopamine = self.dopamine;
+
+        let skip = self.compressed_memories.len().saturating_sub(INHERITED_MEMORIES);
+        child.compressed_memories = self.compressed_memories[skip..].to_vec();
+        child.research_topics = self.research_topics.iter()
+            .take(INHERITED_RESEARCH_TOPICS)
+            .cloned()
             .collect();
+        child
+    }
 
-        self.enhanced_state.update(&self.neighborhood, &neighbor_states);
-        
-        let effects = self.calculate_interaction_effects(&neighbor_states);
-        self.process_interaction_effects(effects);
-
-        if self.should_generate_thought() {
-            let context = self.get_current_focus();
-            self.generate_thought(api_client, &context).await?;
+    /// A child of this cell and `partner`. Each axis is a random blend of the
+    /// two parents, then mutated at their average rate; research topics and
+    /// latest memories alternate between the parents.
+    pub fn crossover(&self, partner: &Cell, position: Coordinates, rng: &mut impl Rng) -> Cell {
+        let mut child = Cell::new(position);
+        child.parent_id = Some(self.id);
+        child.co_parent_id = Some(partner.id);
+
+        let mutation_rate = (self.mutation_rate + partner.mutation_rate) / 2.0;
+        let spread = DNA_MUTATION_SPREAD * mutation_rate;
+        let (ours, theirs) = (self.dimensional_position.axes(), partner.dimensional_position.axes());
+        for (i, axis) in child.dimensional_position.axes_mut().into_iter().enumerate() {
+            let blend = rng.gen::<f64>();
+            let mixed = ours[i] * blend + theirs[i] * (1.0 - blend);
+            *axis = (mixed + rng.gen_range(-1.0..=1.0) * spread).clamp(-100.0, 100.0);
         }
+        child.mutation_rate = (mutation_rate + rng.gen_range(-1.0..=1.0) * MUTATION_RATE_DRIFT).clamp(0.0, 1.0);
+        child.dopamine = (self.dopamine + partner.dopamine) / 2.0;
+
+        child.research_topics = interleave(&self.research_topics, &partner.research_topics, INHERITED_RESEARCH_TOPICS);
+        let latest = |cell: &Cell| cell.compressed_memories.iter().rev().cloned().collect::<Vec<_>>();
+        child.compressed_memories = interleave(&latest(self), &latest(partner), INHERITED_MEMORIES);
+        child.compressed_memories.reverse();
+        child
+    }
 
-        Ok(())
+    /// Takes up to `amount` energy; a cell can't go below zero.
+    pub fn spend_energy(&mut self, amount: f64) {
+        let spent = amount.min(self.energy).max(0.0);
+        self.energy -= spent;
+        self.energy_spent += spent;
+    }
+
+    pub fn is_dormant(&self) -> bool {
+        self.dormant_cycles.is_some()
+    }
+
+    /// The state neighbours see during the LTL phase, with energy and
+    /// activity taken from the cell itself and the Lenia field under it.
+    pub fn ltl_state(&self) -> EnhancedCellState {
+        let mut state = self.enhanced_state.clone();
+        state.energy = self.energy;
+        state.activity_level = 0.5 * state.activity_level + 0.5 * self.lenia_state;
+        state
     }
 
-    fn should_generate_thought(&self) -> bool {
+    /// Updates against the neighbours' states and applies the interaction
+    /// effects that only touch this cell. Effects involving other cells or the
+    /// population are returned for the colony to apply.
+    pub fn apply_ltl_rules(
+        &mut self,
+        other_cells: &[(Uuid, Coordinates)],
+        neighbor_states: &HashMap<Uuid, EnhancedCellState>,
+        rules: &LtlRules,
+    ) -> Vec<InteractionEffect> {
+        self.enhanced_state = self.ltl_state();
+        self.neighborhood.update_neighbors(&self.position, self.enhanced_state.phase, other_cells, neighbor_states);
+        self.enhanced_state.update(&self.neighborhood, neighbor_states);
+        self.enhanced_state.couple_phases(&self.neighborhood, neighbor_states);
+        self.energy = self.enhanced_state.energy.clamp(0.0, 100.0);
+        self.phase = self.enhanced_state.phase;
+
+        let effects = self.calculate_interaction_effects(neighbor_states, rules);
+        self.process_interaction_effects(effects)
+    }
+
+    pub fn should_generate_thought(&self) -> bool {
         self.enhanced_state.stability > 0.5 && 
         self.enhanced_state.activity_level > 0.3 &&
         rand::random::<f64>() < self.mutation_rate // Use mutation_rate directly for thought generation
     }
 
-    fn calculate_interaction_effects(&self, neighbor_states: &HashMap<Uuid, EnhancedCellState>) -> Vec<InteractionEffect> {
+    fn calculate_interaction_effects(&self, neighbor_states: &HashMap<Uuid, EnhancedCellState>, rules: &LtlRules) -> Vec<InteractionEffect> {
         let mut effects = Vec::new();
+        let neighbors: Vec<(Uuid, &EnhancedCellState)> = self.neighborhood.neighbors.keys()
+            .filter_map(|id| neighbor_states.get(id).map(|state| (*id, state)))
+            .collect();
         
-        // Energy gradient effects
-        let avg_neighbor_energy: f64 = neighbor_states.values()
-            .map(|state| state.energy)
-            .sum::<f64>() / neighbor_states.len() as f64;
-        
-        let energy_gradient = (avg_neighbor_energy - self.enhanced_state.energy) / 100.0;
-        if energy_gradient > 0.5 && self.enhanced_state.stability > 0.7 {
-            effects.push(InteractionEffect::EnergyBoost(energy_gradient * 5.0));
-        }
+        if !neighbors.is_empty() {
+            // Energy flows from the richest neighbour when the neighbourhood is well ahead
+            let avg_neighbor_energy: f64 = neighbors.iter()
+                .map(|(_, state)| state.energy)
+                .sum::<f64>() / neighbors.len() as f64;
+            
+            let energy_gradient = (avg_neighbor_energy - self.enhanced_state.energy) / 100.0;
+            if energy_gradient > LTL_TRANSFER_GRADIENT && self.enhanced_state.stability > 0.7 {
+                if let Some((donor, _)) = neighbors.iter().max_by(|a, b| a.1.energy.total_cmp(&b.1.energy)) {
+                    effects.push(InteractionEffect::EnergyTransfer { from: *donor, amount: energy_gradient * 5.0 });
+                }
+            }
 
-        // Phase synchronization
-        let phase_alignment = neighbor_states.values()
-            .map(|state| (state.phase - self.enhanced_state.phase).cos())
-            .sum::<f64>() / neighbor_states.len() as f64;
-        
-        if phase_alignment > 0.8 {
-            effects.push(InteractionEffect::SynchronizationBonus(phase_alignment * 2.0));
+            // Phase synchronization
+            let phase_alignment = neighbors.iter()
+                .map(|(_, state)| (state.phase - self.enhanced_state.phase).cos())
+                .sum::<f64>() / neighbors.len() as f64;
+            
+            if phase_alignment > 0.8 {
+                effects.push(InteractionEffect::SynchronizationBonus(phase_alignment * 2.0));
+            }
         }
 
         // Reproduction conditions
-        if self.enhanced_state.activity_level > 0.7 && self.enhanced_state.energy > 50.0 {
+        let neighbor_count = neighbors.len();
+        if self.enhanced_state.activity_level > 0.7
+            && self.enhanced_state.energy > 50.0
+            && rules.allows_birth(neighbor_count)
+        {
             effects.push(InteractionEffect::SpawnConditionsMet);
         }
+        if !rules.allows_survival(neighbor_count) {
+            effects.push(InteractionEffect::SurvivalFailed);
+        }
 
         effects
     }
 
-    fn process_interaction_effects(&mut self, effects: Vec<InteractionEffect>) {
+    // Applies the effects local to this cell and returns the rest
+    fn process_interaction_effects(&mut self, effects: Vec<InteractionEffect>) -> Vec<InteractionEffect> {
+        let mut colony_effects = Vec::new();
         for effect in effects {
             match effect {
-                InteractionEffect::EnergyBoost(amount) => {
-                    self.enhanced_state.energy += amount;
-                    self.energy += amount;
-                },
                 InteractionEffect::SynchronizationBonus(bonus) => {
-                    self.enhanced_state.stability += bonus * 0.1;
-                    self.dopamine += bonus * 0.05;
+                    self.enhanced_state.stability = (self.enhanced_state.stability + bonus * 0.1).min(1.0);
+                    self.dopamine = (self.dopamine + bonus * 0.05).min(1.0);
+                },
+                InteractionEffect::SurvivalFailed => {
+                    self.energy = (self.energy - LTL_SURVIVAL_PENALTY).max(0.0);
+                    self.enhanced_state.energy = self.energy;
+                    colony_effects.push(effect);
                 },
-                InteractionEffect::SpawnConditionsMet => {
-                    self.enhanced_state.energy *= 0.7;
-                    self.energy *= 0.7;
+                InteractionEffect::EnergyTransfer { .. } | InteractionEffect::SpawnConditionsMet => {
+                    colony_effects.push(effect);
                 }
             }
         }
+        colony_effects
     }
 
     pub async fn generate_thought(
         &mut self,
         api_client: &dyn ModelClient,  // Changed from &Box<dyn ModelClient>
+        embedding_client: Option<&dyn EmbeddingClient>,
+        colony_recent: &[(Uuid, Thought)],
         mission: &str,
+        inbox: &[String],
     ) -> Result<(), Box<dyn std::error::Error>> {
+        let repairs_before = repair_stats().attempts;
+
         // First evaluate dimensional state
         let recent_thoughts: Vec<_> = self.thoughts.iter().rev().take(5).cloned().collect();
         let recent_plans: Vec<_> = self.compressed_memories.iter()
@@ -182,6 +286,7 @@ impl Cell {
         let (energy_impact, dopamine_impact) = api_client
             .evaluate_dimensional_state(&self.dimensional_position, &recent_thoughts, &recent_plans)
             .await?;
+        self.spend_energy(ENERGY_COST_PER_LLM_CALL);
             
         // Apply impacts
         self.energy = (self.energy + energy_impact).clamp(0.0, 100.0);
@@ -197,6 +302,7 @@ impl Cell {
             energy_level: self.energy,
             dimensional_position: self.dimensional_position.clone(),
             dopamine: self.dopamine,
+            inbox: inbox.to_vec(),
         };
 
         // Get real-time context with recent thoughts for better context
@@ -206,52 +312,17 @@ impl Cell {
             .map(|t| t.content.clone())
             .collect();
         let real_time_context = api_client.gather_real_time_context(Some(recent_thought_contents)).await?;
+        self.spend_energy(ENERGY_COST_PER_LLM_CALL);
         
         let (thought_content, relevance_score, factors) = api_client
             .generate_contextual_thought(&cell_context, &real_time_context, mission)
             .await?;
+        self.spend_energy(ENERGY_COST_PER_LLM_CALL);
+        // Repair prompts sent for malformed responses cost the same as any call
+        self.spend_energy(ENERGY_COST_PER_LLM_CALL * repair_stats().attempts.saturating_sub(repairs_before) as f64);
 
         // Parse dimensional scores from thought content
-        for line in thought_content.lines() {
-            let line = line.trim();
-            if line.starts_with("- EMERGENT_INTELLIGENCE:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.emergence = score.clamp(-100.0, 100.0);
-                    }
-                }
-            } else if line.starts_with("- RESOURCE_EFFICIENCY:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.efficiency = score.clamp(-100.0, 100.0);
-                    }
-                }
-            } else if line.starts_with("- NETWORK_COHERENCE:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.coherence = score.clamp(-100.0, 100.0);
-                    }
-                }
-            } else if line.starts_with("- GOAL_ALIGNMENT:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.intelligence = score.clamp(-100.0, 100.0);
-                    }
-                }
-            } else if line.starts_with("- TEMPORAL_RESILIENCE:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.resilience = score.clamp(-100.0, 100.0);
-                    }
-                }
-            } else if line.starts_with("- DIMENSIONAL_INTEGRATION:") {
-                if let Some(score_str) = line.split(':').nth(1) {
-                    if let Ok(score) = score_str.trim().parse::<f64>() {
-                        self.dimensional_position.integration = score.clamp(-100.0, 100.0);
-                    }
-                }
-            }
-        }
+        DimensionReadings::parse(&thought_content).apply_to(&mut self.dimensional_position);
 
         let mut inputs = Vec::new();
         let mut outputs = Vec::new();
@@ -303,7 +374,7 @@ impl Cell {
         );
         // Extract ASCII visualization and referenced thoughts from the thought content
         let mut ascii_viz = None;
-        let mut referenced_thoughts = Vec::new();
+        let referenced_thoughts = parse_references(&thought_content);
             
         for line in thought_content.lines() {
             if line.starts_with("ASCII_TEMPLATE:") {
@@ -312,16 +383,6 @@ impl Cell {
                         ascii_viz = Some(template.to_string());
                     }
                 }
-            } else if line.starts_with("REFERENCES:") {
-                if let Some(refs) = line.split(':').nth(1) {
-                    for reference in refs.split(',') {
-                        if let Some((cell_id, thought_id)) = reference.trim().split_once('/') {
-                            if let (Ok(cell_uuid), id) = (Uuid::parse_str(cell_id), thought_id.to_string()) {
-                                referenced_thoughts.push((cell_uuid, id));
-                            }
-                        }
-                    }
-                }
             }
         }
 
@@ -335,6 +396,8 @@ impl Cell {
             confidence_score: self.calculate_confidence_score(&real_time_context),
             ascii_visualization: ascii_viz,
             referenced_thoughts,
+            embedding: None,
+            novelty_score: 1.0,
         };
         println!("
 Generated Thought:");
@@ -363,7 +426,7 @@ Generated Thought:");
             eprintln!("Error logging thought to file: {}", e);
         }
         
-        self.thoughts.push_back(thought);
+        self.store_thought(thought, embedding_client, colony_recent).await;
         self.check_and_compress_memories(api_client).await?;
         self.update_focus_based_on_context(&real_time_context);
         
@@ -373,6 +436,54 @@ Generated Thought:");
         Ok(())
     }
 
+    /// Embeds the thought when an embedding client is configured, scores its
+    /// novelty against this cell's history and other cells' recent thoughts,
+    /// and rewards the cell accordingly. Near-duplicates of the cell's own
+    /// thoughts are merged into them; near-duplicates of other cells' thoughts
+    /// are dropped. Returns whether the thought was stored as a new one.
+    pub async fn store_thought(
+        &mut self,
+        mut thought: Thought,
+        embedding_client: Option<&dyn EmbeddingClient>,
+        colony_recent: &[(Uuid, Thought)],
+    ) -> bool {
+        if let Some(client) = embedding_client {
+            match client.embed(std::slice::from_ref(&thought.content)).await {
+                Ok(mut embeddings) => thought.embedding = embeddings.pop(),
+                Err(e) => eprintln!("Error embedding thought {}: {}", thought.id, e),
+            }
+        }
+
+        let closest = closest_thought(
+            &thought,
+            self.thoughts.iter(),
+            colony_recent.iter().filter(|(id, _)| *id != self.id).map(|(_, t)| t),
+        );
+        thought.novelty_score = closest.novelty();
+        self.reward_novelty(thought.novelty_score);
+
+        if closest.similarity() < DUPLICATE_THOUGHT_SIMILARITY {
+            self.thoughts.push_back(thought);
+            self.spend_energy(ENERGY_COST_PER_THOUGHT);
+            return true;
+        }
+
+        self.duplicate_thoughts += 1;
+        if let ClosestThought::Own { index, .. } = closest {
+            if let Some(existing) = self.thoughts.get_mut(index) {
+                merge_duplicate(existing, thought);
+            }
+        }
+        false
+    }
+
+    // Novelty above 0.5 earns dopamine and energy, repetition costs them
+    fn reward_novelty(&mut self, novelty: f64) {
+        let reward = novelty - 0.5;
+        self.dopamine = (self.dopamine + reward * NOVELTY_DOPAMINE_GAIN).clamp(0.0, 1.0);
+        self.energy = (self.energy + reward * NOVELTY_ENERGY_GAIN).clamp(0.0, 100.0);
+    }
+
     pub async fn check_and_compress_memories(
         &mut self,
         api_client: &dyn ModelClient  // Changed from &Box<dyn ModelClient>
@@ -386,6 +497,7 @@ Generated Thought:");
                 .collect();
 
             let compressed = api_client.compress_memories(&thoughts_to_compress).await?;
+            self.spend_energy(ENERGY_COST_PER_LLM_CALL);
             self.compressed_memories.push(compressed);
         }
 
@@ -416,8 +528,41 @@ Generated Thought:");
             .collect()
     }
 
+    /// The latest exchanges with other cells, newest first.
     pub fn get_collaboration_history(&self) -> Vec<String> {
-        vec!["Previous collaborations".to_string()]
+        self.collaborations.iter()
+            .rev()
+            .take(COLLABORATIONS_IN_CONTEXT)
+            .map(|c| format!("{} ({})", c.note, c.at.format("%H:%M")))
+            .collect()
+    }
+
+    pub fn record_collaboration(&mut self, peer: Uuid, note: String) {
+        self.collaborations.push_back(Collaboration { peer, note, at: Utc::now() });
+        if self.collaborations.len() > COLLABORATION_HISTORY_LEN {
+            self.collaborations.pop_front();
+        }
+    }
+
+    /// Distinct cells this one has exchanged messages or citations with.
+    pub fn collaborator_count(&self) -> usize {
+        self.collaborations.iter()
+            .map(|c| c.peer)
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    /// Logs each message a thought was built from as an exchange with its sender.
+    pub fn receive(&mut self, messages: &[Message]) {
+        for message in messages {
+            let peer = &message.from.to_string()[..8];
+            let note = match (message.kind, &message.thought_id) {
+                (MessageKind::Thought, Some(thought_id)) => format!("Read thought {} from {}", thought_id, peer),
+                (MessageKind::Thought, None) => format!("Read a thought from {}", peer),
+                (MessageKind::Request, _) => format!("Asked by {}: {}", peer, message.content.chars().take(80).collect::<String>()),
+            };
+            self.record_collaboration(message.from, note);
+        }
     }
 
     pub fn get_performance_metrics(&self) -> HashMap<String, f64> {
@@ -463,9 +608,9 @@ Generated Thought:");
                     println!("║     Title: {}", node.title);
                     println!("║     Status: {:?}", node.status);
                     println!("║     Progress: [{}{}] {:.1}%",
-                        "█".repeat((node.estimated_completion * 20.0) as usize),
-                        "░".repeat(20 - (node.estimated_completion * 20.0) as usize),
-                        node.estimated_completion * 100.0
+                        "█".repeat((node.progress * 20.0) as usize),
+                        "░".repeat(20 - (node.progress * 20.0) as usize),
+                        node.progress * 100.0
                     );
                     println!("║");
                     println!("║     Description:");
@@ -597,3 +742,63 @@ Generated Thought:");
         self.dopamine = self.dopamine * 0.9 + self.context_alignment_score * 0.1;
     }
 }
+
+// Alternates between `a` and `b`, skipping repeats, until `limit` items
+fn interleave(a: &[String], b: &[String], limit: usize) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for i in 0..a.len().max(b.len()) {
+        for item in [a.get(i), b.get(i)].into_iter().flatten() {
+            if merged.len() < limit && !merged.contains(item) {
+                merged.push(item.clone());
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_offspring_inherits_dna_and_memories() {
+        let mut parent = Cell::new(Coordinates::default());
+        parent.dimensional_position.emergence = 80.0;
+        parent.compressed_memories = (0..5).map(|i| format!("memory {}", i)).collect();
+        parent.research_topics = vec!["swarm consensus".to_string()];
+        parent.mutation_rate = 0.0;
+
+        let child = parent.offspring(Coordinates::default(), &mut StdRng::seed_from_u64(3));
+        assert_eq!(child.parent_id, Some(parent.id));
+        assert_eq!(child.dimensional_position.axes(), parent.dimensional_position.axes());
+        assert_eq!(child.compressed_memories, ["memory 2", "memory 3", "memory 4"]);
+        assert_eq!(child.research_topics, parent.research_topics);
+
+        parent.mutation_rate = 1.0;
+        let child = parent.offspring(Coordinates::default(), &mut StdRng::seed_from_u64(3));
+        let drift = (child.dimensional_position.emergence - 80.0).abs();
+        assert!(drift > 0.0 && drift <= DNA_MUTATION_SPREAD);
+    }
+
+    #[test]
+    fn test_crossover_mixes_both_parents() {
+        let mut first = Cell::new(Coordinates::default());
+        let mut second = Cell::new(Coordinates::default());
+        first.mutation_rate = 0.0;
+        second.mutation_rate = 0.0;
+        first.dimensional_position.emergence = -60.0;
+        second.dimensional_position.emergence = 40.0;
+        first.research_topics = vec!["a".to_string(), "shared".to_string()];
+        second.research_topics = vec!["shared".to_string(), "b".to_string()];
+        first.compressed_memories = vec!["old first".to_string(), "new first".to_string()];
+        second.compressed_memories = vec!["new second".to_string()];
+
+        let child = first.crossover(&second, Coordinates::default(), &mut StdRng::seed_from_u64(9));
+        assert_eq!((child.parent_id, child.co_parent_id), (Some(first.id), Some(second.id)));
+        assert!((-60.0..=40.0).contains(&child.dimensional_position.emergence));
+        assert_eq!(child.research_topics, ["a", "shared", "b"]);
+        assert_eq!(child.compressed_memories, ["old first", "new second", "new first"]);
+    }
+}
@@ -8,16 +8,37 @@ The above copyright notice and this permission notice shall be included in all c
 
 THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
 
-use crate::models::types::{CellContext, Coordinates, Plan, PlanStatus, ColonyStatistics, Thought, DimensionalPosition};
+use crate::models::types::{CellContext, Coordinates, Plan, PlanNode, PlanNodeStatus, PlanStatus, ColonyStatistics, Thought, DimensionalPosition};
 use crate::utils::logging::*;
-use crate::api::ModelClient;
+use crate::api::{EmbeddingClient, ModelClient};
+use crate::api::embeddings::{cosine_similarity, select_diverse};
+use crate::api::prompts::render_prompt;
+use crate::api::validation::{repair_stats, validate_plan, validate_with_repair, DimensionReadings, DIMENSION_AXES};
+use crate::models::plan_graph::{parse_plan_nodes, to_components};
 use std::error::Error;
 use std::path::Path;
 use std::collections::VecDeque;
 use crate::models::plan_analysis::{PlanAnalysis, save_plan_to_file};
-use crate::models::constants::{MAX_THOUGHTS_FOR_PLAN, NEIGHBOR_DISTANCE_THRESHOLD, BATCH_SIZE};
+use crate::models::constants::{MAX_THOUGHTS_FOR_PLAN, NEIGHBOR_DISTANCE_THRESHOLD, SPATIAL_BUCKET_SIZE, BATCH_SIZE, PLAN_THOUGHT_RELEVANCE_WEIGHT, RECENT_THOUGHTS_PER_CELL};
+use crate::models::constants::{LENIA_GRID_SIZE, LENIA_KERNEL_RADIUS, LENIA_STEPS_PER_CYCLE, LENIA_DEPOSIT_RADIUS, LENIA_DEPOSIT_STRENGTH, LENIA_DIMENSION_COUPLING, LENIA_DIMENSION_MEMORY, LENIA_BOUNDS_MARGIN};
+use crate::models::constants::LTL_OFFSPRING_ENERGY_SHARE;
+use crate::models::constants::{CARRYING_CAPACITY, CELL_ARCHIVE_DIR, DORMANCY_MAX_CYCLES, DORMANCY_WAKE_ENERGY};
+use crate::models::constants::{ENERGY_COST_PER_LLM_CALL, METABOLIC_COST_PER_CYCLE, RESOURCE_REGEN_PER_CYCLE};
+use crate::models::state::CellArchive;
+use crate::models::constants::{PLAN_PROGRESS_PER_CYCLE, PLAN_STEP_ENERGY_REWARD, PLAN_STEP_DOPAMINE_REWARD};
+use crate::models::constants::{PLAN_COMPLETION_ENERGY_REWARD, PLAN_COMPLETION_DOPAMINE_REWARD, PLAN_FAILURE_DOPAMINE_PENALTY};
+use crate::models::constants::{CONSENSUS_QUORUM, PLAN_MERGE_EMBEDDING_SIMILARITY, PLAN_MERGE_MAX_THOUGHTS, PLAN_MERGE_SHINGLE_SIMILARITY};
+use crate::models::constants::{LINEAGE_DIR, CROSSOVER_MIN_COMPLEMENT, CROSSOVER_MIN_ENERGY};
+use crate::systems::lineage::Lineage;
+use crate::systems::spatial::SpatialIndex;
+use crate::systems::movement::{MovementRules, SensedNeighbor};
+use crate::systems::execution::{advance, parse_verdict, rule_pass_chance, settle, PlanEvaluator};
+use crate::systems::consensus::{centroid, cluster_plans, plan_text, tally, vote, Ballot};
+use crate::systems::messaging::{parse_references, parse_requests, Message, MessageBus, MessageKind, Recipients};
 use crate::api::openrouter::OpenRouterClient;
 use crate::systems::cell::Cell;
+use crate::systems::ltl::{phase_order, EnhancedCellState, InteractionEffect, LtlRules};
+use crate::systems::lenia::{blob_pattern, ChannelKernel, GridTransform, GrowthFunction, KernelShape, LeniaParams, LeniaWorld};
 use std::collections::HashMap;
 use chrono::Utc;
 use uuid::Uuid;
@@ -27,14 +48,63 @@ pub struct Colony {
     pub cells: HashMap<Uuid, Cell>,
     pub mission: String,
     pub api_client: Box<dyn ModelClient>,  // Change this line
+    pub embedding_client: Option<Box<dyn EmbeddingClient>>,
+    mission_embedding: Option<Vec<f32>>,
     pub cell_positions: HashMap<Uuid, Coordinates>,
+    spatial: SpatialIndex,
     plan_leaderboard: HashMap<Uuid, (usize, usize)>,
+    pub lenia_world: LeniaWorld,
+    lenia_last_step: chrono::DateTime<Utc>,
+    pub ltl_rules: LtlRules,
+    // Cells whose LTL effects allowed them to bud this cycle
+    pending_spawns: Vec<Uuid>,
+    pub carrying_capacity: usize,
+    deaths: u64,
+    // Energy spent by cells that have since died
+    archived_energy_spent: f64,
+    pub lineage: Lineage,
+    cycle: u64,
+    pub reproduction_mode: ReproductionMode,
+    // Whether crossover children get an LLM-merged memory of both parents
+    crossover_llm_merge: bool,
+    pub movement: MovementRules,
+    messages: MessageBus,
+    pub plan_evaluator: PlanEvaluator,
+    plans_completed: u64,
+    plans_failed: u64,
+    // Whether similar proposals are merged and voted on before execution
+    consensus: bool,
+    master_plan: Option<Uuid>,
+}
+
+/// How cells that meet the birth rule reproduce. `Mixed` crosses over with a
+/// complementary neighbour when there is one and buds otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReproductionMode {
+    Budding,
+    Crossover,
+    Mixed,
+}
+
+impl std::str::FromStr for ReproductionMode {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "budding" => Ok(ReproductionMode::Budding),
+            "crossover" => Ok(ReproductionMode::Crossover),
+            "mixed" => Ok(ReproductionMode::Mixed),
+            _ => Err(format!("Unknown reproduction mode '{}'", mode)),
+        }
+    }
 }
 impl Colony {
 
     pub async fn process_cell_sub_batch(&mut self, cell_ids: &[Uuid]) -> Result<(), Box<dyn Error>> {
         // Get a reference to the trait object once at the start
         let api_client: &dyn ModelClient = self.api_client.as_ref();
+        let embedding_client = self.embedding_client.as_deref();
+        let mut colony_recent = self.recent_colony_thoughts();
 
         let thoughts: Vec<_> = cell_ids.iter()
             .filter_map(|id| self.cells.get(&id))
@@ -55,20 +125,33 @@ impl Colony {
         let mut success_count = 0;
         let mut error_count = 0;
         let mut timeout_count = 0;
+        let mut published = Vec::new();
 
         // Process each cell directly in self.cells
         for &cell_id in cell_ids {
+            let messages = self.messages.take(cell_id, self.cycle);
+            let mut thought_generated = false;
             if let Some(cell) = self.cells.get_mut(&cell_id) {
+                let inbox: Vec<String> = messages.iter().map(Message::summary).collect();
+                let previous = cell.thoughts.back().map(|t| t.id.clone());
                 let mut retries = 3;
                 let mut delay = std::time::Duration::from_secs(1);
                 
                 while retries > 0 {
                     match tokio::time::timeout(
                         std::time::Duration::from_secs(180),
-                        cell.generate_thought(api_client, &self.mission)
+                        cell.generate_thought(api_client, embedding_client, &colony_recent, &self.mission, &inbox)
                     ).await {
                         Ok(Ok(_)) => {
                             success_count += 1;
+                            thought_generated = true;
+                            cell.receive(&messages);
+                            if let Some(thought) = cell.thoughts.back() {
+                                if previous.as_ref() != Some(&thought.id) {
+                                    colony_recent.push((cell_id, thought.clone()));
+                                    published.push((cell_id, thought.id.clone()));
+                                }
+                            }
                             log_success(&format!("Generated thought for cell {}", cell_id));
                             break;
                         }
@@ -100,10 +183,16 @@ impl Colony {
             } else {
                 log_error(&format!("Cell {} not found", cell_id));
             }
+            if !thought_generated {
+                self.messages.requeue(cell_id, messages);
+            }
         }
 
         log_info(&format!("Batch processing complete: {} successes, {} errors, {} timeouts",
             success_count, error_count, timeout_count));
+        for (cell_id, thought_id) in published {
+            self.publish_thought(cell_id, &thought_id);
+        }
 
         Ok(())
     }
@@ -114,11 +203,93 @@ impl Colony {
             cells: HashMap::new(),
             mission: mission.to_string(),
             api_client,
+            embedding_client: None,
+            mission_embedding: None,
             cell_positions: HashMap::new(),
+            spatial: SpatialIndex::new(SPATIAL_BUCKET_SIZE),
             plan_leaderboard: HashMap::new(),
+            lenia_world: LeniaWorld::new(colony_lenia_params()),
+            lenia_last_step: Utc::now(),
+            ltl_rules: LtlRules::default(),
+            pending_spawns: Vec::new(),
+            carrying_capacity: CARRYING_CAPACITY,
+            deaths: 0,
+            archived_energy_spent: 0.0,
+            lineage: Lineage::default(),
+            cycle: 0,
+            reproduction_mode: ReproductionMode::Mixed,
+            crossover_llm_merge: false,
+            movement: MovementRules::default(),
+            messages: MessageBus::default(),
+            plan_evaluator: PlanEvaluator::Rules,
+            plans_completed: 0,
+            plans_failed: 0,
+            consensus: true,
+            master_plan: None,
         }
     }
 
+    pub fn with_embedding_client(mut self, embedding_client: Box<dyn EmbeddingClient>) -> Self {
+        self.embedding_client = Some(embedding_client);
+        self
+    }
+
+    pub fn with_carrying_capacity(mut self, carrying_capacity: usize) -> Self {
+        self.carrying_capacity = carrying_capacity.max(1);
+        self
+    }
+
+    // The given cells that are awake; dormant cells neither think nor plan
+    fn awake_cells(&self, cell_ids: &[Uuid]) -> Vec<Uuid> {
+        cell_ids.iter()
+            .copied()
+            .filter(|id| self.cells.get(id).is_some_and(|cell| !cell.is_dormant()))
+            .collect()
+    }
+
+    pub fn with_reproduction(mut self, mode: ReproductionMode, llm_merge: bool) -> Self {
+        self.reproduction_mode = mode;
+        self.crossover_llm_merge = llm_merge;
+        self
+    }
+
+    pub fn with_plan_evaluator(mut self, plan_evaluator: PlanEvaluator) -> Self {
+        self.plan_evaluator = plan_evaluator;
+        self
+    }
+
+    pub fn with_consensus(mut self, enabled: bool) -> Self {
+        self.consensus = enabled;
+        self
+    }
+
+    pub fn with_movement_rules(mut self, movement: MovementRules) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    pub fn with_ltl_rules(mut self, ltl_rules: LtlRules) -> Self {
+        self.ltl_rules = ltl_rules;
+        self
+    }
+
+    /// Replaces the default field. Dimension coupling only runs when the world
+    /// has a channel for every axis.
+    pub fn with_lenia_world(mut self, lenia_world: LeniaWorld) -> Self {
+        self.lenia_world = lenia_world;
+        self
+    }
+
+    // The latest thoughts of every cell, for novelty scoring
+    fn recent_colony_thoughts(&self) -> Vec<(Uuid, Thought)> {
+        self.cells.iter()
+            .flat_map(|(id, cell)| cell.thoughts.iter()
+                .rev()
+                .take(RECENT_THOUGHTS_PER_CELL)
+                .map(move |thought| (*id, thought.clone())))
+            .collect()
+    }
+
     fn analyze_dimensional_balance(&self, cell_ids: &[Uuid]) -> (DimensionalPosition, f64) {
         let mut combined = DimensionalPosition {
             emergence: 0.0,
@@ -169,18 +340,11 @@ impl Colony {
         use tokio::time::timeout;
         use std::time::Duration;
         
+        let awake = self.awake_cells(cell_ids);
+        let cell_ids = &awake[..];
         log_timestamp(&format!("Starting batch processing of {} cells", cell_ids.len()));
         let api_client: &dyn ModelClient = self.api_client.as_ref();
 
-        // Collect recent thoughts from all cells
-        let mut all_recent_thoughts = Vec::new();
-        for cell in self.cells.values() {
-            all_recent_thoughts.extend(cell.thoughts.iter()
-                .rev()
-                .take(10)
-                .cloned());
-        }
-        
         let batch_id = Uuid::new_v4();
         log_metric("Batch ID", batch_id);
         log_metric("Processing Mode", "Batch Evolution");
@@ -267,6 +431,11 @@ impl Colony {
                 .unwrap_or(0)
         );
         
+        // Cells read what their neighbours sent them since their last thought
+        let mut inboxes: HashMap<Uuid, Vec<Message>> = cell_ids.iter()
+            .map(|&id| (id, self.messages.take(id, self.cycle)))
+            .collect();
+
         // Sort cells by dimensional scores and prepare contexts
         let mut ranked_cells: Vec<(&Uuid, &Cell)> = cell_ids.iter()
             .filter_map(|id| self.cells.get(id).map(|cell| (id, cell)))
@@ -303,6 +472,7 @@ impl Colony {
                 energy_level: cell.energy,
                 dimensional_position: cell.dimensional_position.clone(),
                 dopamine: cell.dopamine,
+                inbox: inboxes.get(*id).map(|messages| messages.iter().map(Message::summary).collect()).unwrap_or_default(),
             }))
             .collect();
 
@@ -331,6 +501,7 @@ impl Colony {
         
         println!("║ [{}] Generating thoughts...", 
             chrono::Local::now().format("%H:%M:%S"));
+        let repairs_before = repair_stats().attempts;
         let batch_results = match tokio::time::timeout(
             std::time::Duration::from_secs(300), // Reduced timeout
             self.api_client.generate_contextual_thoughts_batch(&cell_context_refs, &real_time_context, &self.mission, &[])
@@ -348,14 +519,23 @@ impl Colony {
             }
         };
         
+        let batch_cells: Vec<Uuid> = cell_contexts.iter().map(|(id, _)| *id).collect();
+        self.charge_llm_calls(&batch_cells, repair_stats().attempts.saturating_sub(repairs_before));
         println!("║   Thoughts Generated: {}", batch_results.len());
         println!("║   Success Rate: {:.1}%", 
             (batch_results.len() as f64 / cell_contexts.len() as f64) * 100.0);
 
+        let mut colony_recent = self.recent_colony_thoughts();
+        let mut published = Vec::new();
+
         // Update cells with their new thoughts, adjusting dimensional positions 
         for (cell_id, thoughts) in batch_results {
             if let Some(cell) = self.cells.get(&cell_id).cloned() {
                 let mut updated_cell = cell;
+                updated_cell.spend_energy(ENERGY_COST_PER_LLM_CALL);
+                if let Some(messages) = inboxes.remove(&cell_id) {
+                    updated_cell.receive(&messages);
+                }
             
                 // Adjust dimensional position based on imbalance
                 let adjustment = 0.1 * (1.0 - imbalance.min(1.0));
@@ -380,58 +560,35 @@ impl Colony {
                             energy_level: updated_cell.energy,
                             dimensional_position: updated_cell.dimensional_position.clone(),
                             dopamine: updated_cell.dopamine,
+                            inbox: Vec::new(),
                         }),
                         real_time_factors,
                         confidence_score: updated_cell.calculate_confidence_score(&real_time_context),
                         ascii_visualization: None,
-                        referenced_thoughts: Vec::new(),
+                        referenced_thoughts: parse_references(&thought_content),
+                        embedding: None,
+                        novelty_score: 1.0,
                     };
 
-                    // Parse dimensional positions from thought content
-                    for line in thought_content.lines() {
-                        let line = line.trim();
-                        if line.starts_with("DIMENSIONS:") {
-                            continue;
-                        }
-                        if line.starts_with("- EMERGENT_INTELLIGENCE:") {
-                            updated_cell.dimensional_position.emergence = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("- RESOURCE_EFFICIENCY:") {
-                            updated_cell.dimensional_position.efficiency = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("- NETWORK_COHERENCE:") {
-                            updated_cell.dimensional_position.coherence = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("- GOAL_ALIGNMENT:") {
-                            updated_cell.dimensional_position.intelligence = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("- TEMPORAL_RESILIENCE:") {
-                            updated_cell.dimensional_position.resilience = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("- DIMENSIONAL_INTEGRATION:") {
-                            updated_cell.dimensional_position.integration = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.0);
-                        } else if line.starts_with("DOPAMINE:") {
-                            updated_cell.dopamine = line.split(':')
-                                .nth(1)
-                                .and_then(|s| s.trim().parse().ok())
-                                .unwrap_or(0.5);
-                        }
+                    // Only axes the model actually reported move; the rest keep their value
+                    let readings = DimensionReadings::parse(&thought_content);
+                    readings.apply_to(&mut updated_cell.dimensional_position);
+                    if let Some(dopamine) = readings.dopamine {
+                        updated_cell.dopamine = dopamine;
+                    }
+                    let missing = readings.missing_axes();
+                    if !missing.is_empty() {
+                        log_warning(&format!("Cell {} thought had no usable value for {}; keeping previous", cell_id, missing.join(", ")));
                     }
                     
-                    updated_cell.thoughts.push_back(thought);
+                    if updated_cell.store_thought(thought, self.embedding_client.as_deref(), &colony_recent).await {
+                        if let Some(stored) = updated_cell.thoughts.back() {
+                            colony_recent.push((cell_id, stored.clone()));
+                            published.push((cell_id, stored.id.clone()));
+                        }
+                    } else {
+                        log_info(&format!("Cell {} repeated an existing thought; merged or dropped", cell_id));
+                    }
                     if let Err(e) = updated_cell.check_and_compress_memories(self.api_client.as_ref()).await {
                         eprintln!("Error compressing memories: {}", e);
                     }
@@ -440,6 +597,13 @@ impl Colony {
                 self.cells.insert(cell_id, updated_cell);
             }
         }
+        // Cells the model returned nothing for keep their messages
+        for (cell_id, messages) in inboxes {
+            self.messages.requeue(cell_id, messages);
+        }
+        for (cell_id, thought_id) in published {
+            self.publish_thought(cell_id, &thought_id);
+        }
 
         Ok(())
     }
@@ -447,6 +611,11 @@ impl Colony {
     pub async fn create_plans_batch(&mut self, cell_ids: &[Uuid], cycle_id: &str) -> Result<(), Box<dyn std::error::Error>> {
         use crate::utils::logging::*;
         
+        // Cells already carrying out a plan keep it until it completes or fails
+        let awake: Vec<Uuid> = self.awake_cells(cell_ids).into_iter()
+            .filter(|id| !self.is_executing(*id))
+            .collect();
+        let cell_ids = &awake[..];
         log_timestamp(&format!("Starting plan generation for {} cells", cell_ids.len()));
         
         let batch_id = Uuid::new_v4();
@@ -456,11 +625,25 @@ impl Colony {
         log_memory_usage("Batch Memory", std::mem::size_of::<Cell>() * cell_ids.len());
         
         let mut updates = Vec::new();
+        let mut planners = Vec::new();
+        let mut claimed = std::collections::HashSet::new();
         let mut best_plan_score = 0.0;
         let mut best_plan_narrative = String::new();
         let start_time = std::time::Instant::now();
         
+        if self.mission_embedding.is_none() {
+            if let Some(client) = self.embedding_client.as_deref() {
+                match client.embed(std::slice::from_ref(&self.mission)).await {
+                    Ok(mut embeddings) => self.mission_embedding = embeddings.pop(),
+                    Err(e) => eprintln!("Error embedding mission: {}", e),
+                }
+            }
+        }
+
         for &cell_id in cell_ids {
+            if claimed.contains(&cell_id) {
+                continue;
+            }
             if let Some(cell) = self.cells.get(&cell_id).cloned() {
                 // Calculate dimensional differences between cells
                 let mut neighbor_scores: Vec<(Uuid, f64)> = cell.neighbors.iter()
@@ -479,8 +662,9 @@ impl Colony {
                 neighbor_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
                 // Collect thoughts prioritizing complementary cells
-                let mut combined_thoughts = Vec::new();
-                combined_thoughts.extend(cell.thoughts.iter().cloned());
+                let mut candidates: Vec<(Thought, f64)> = cell.thoughts.iter()
+                    .map(|thought| (thought.clone(), thought.relevance_score))
+                    .collect();
 
                 for (neighbor_id, _score) in neighbor_scores {
                     if let Some(neighbor) = self.cells.get(&neighbor_id) {
@@ -496,13 +680,20 @@ impl Colony {
                             .collect();
                         
                         neighbor_thoughts.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                        combined_thoughts.extend(neighbor_thoughts.into_iter().map(|(t, _)| t));
+                        candidates.extend(neighbor_thoughts);
                     }
                 }
 
-                combined_thoughts.truncate(MAX_THOUGHTS_FOR_PLAN);
+                let combined_thoughts = if self.embedding_client.is_some() {
+                    select_plan_thoughts(candidates, self.mission_embedding.as_deref())
+                } else {
+                    let mut combined_thoughts: Vec<Thought> = candidates.into_iter().map(|(t, _)| t).collect();
+                    combined_thoughts.truncate(MAX_THOUGHTS_FOR_PLAN);
+                    combined_thoughts
+                };
 
                 println!("║ Creating plan for cell {} (timeout: 300s)...", cell_id);
+                let repairs_before = repair_stats().attempts;
                 let plan_result = match tokio::time::timeout(
                     std::time::Duration::from_secs(300), // Reduced timeout
                     self.api_client.create_plan(&combined_thoughts)
@@ -523,6 +714,8 @@ impl Colony {
                     }
                 };
                 
+                planners.push((cell_id, 1 + repair_stats().attempts.saturating_sub(repairs_before)));
+
                 println!("║ Cell Details:");
                 println!("║   ID: {}", cell_id);
                 println!("║   Energy Level: {:.2}", cell.energy);
@@ -530,6 +723,7 @@ impl Colony {
                     cell.position.x, cell.position.y, cell.position.z);
                 println!("║   Thoughts: {}", combined_thoughts.len());
                 println!("║   Neighbors: {}", cell.neighbors.len());
+                println!("║   Collaborators: {}", cell.collaborator_count());
                 println!("║   Dimensional Position:");
                 println!("║     Emergence: {:.2}", cell.dimensional_position.emergence);
                 println!("║     Coherence: {:.2}", cell.dimensional_position.coherence);
@@ -540,12 +734,9 @@ impl Colony {
                 println!("║");
                 println!("║   Plan Details:");
                 println!("║     Nodes: {}", plan_result.nodes.len());
-                println!("║     Score: {:.2}", plan_result.score);
-                println!("║     Participating Cells: {}", plan_result.participating_cells.len());
-                
-                // Log the full plan details
-                
                 let plan_score = combined_thoughts.iter().map(|t| t.relevance_score).sum::<f64>() / combined_thoughts.len() as f64;
+                println!("║     Score: {:.2}", plan_score);
+                println!("║     Participating Cells: {}", plan_result.participating_cells.len());
                 
                 let plan = Plan {
                     id: Uuid::new_v4(),
@@ -563,17 +754,19 @@ impl Colony {
                     },
                     score: plan_score,
                     participating_cells: std::iter::once(cell_id)
-                        .chain(cell.neighbors.iter().cloned())
+                        .chain(cell.neighbors.iter().cloned().filter(|id| !self.is_executing(*id) && !claimed.contains(id)))
                         .collect(),
                     created_at: Utc::now(),
                     status: PlanStatus::Proposed,
                 };
 
+                self.lineage.record_plan_score(cell_id, plan_score);
                 if plan_score > best_plan_score {
                     best_plan_score = plan_score;
                     best_plan_narrative = plan_result.summary.clone();
                 }
 
+                claimed.extend(plan.participating_cells.iter().copied());
                 for &participant_id in &plan.participating_cells {
                     if let Some(mut participant) = self.cells.get(&participant_id).cloned() {
                         participant.current_plan = Some(plan.clone());
@@ -595,26 +788,7 @@ impl Colony {
         if !best_plan_narrative.is_empty() {
             println!("
 Analyzing technical context for best plan (score: {:.2})...", best_plan_score);
-            let news_query = format!(
-                r#"Analyze this AI system's plan against posts from the last 72 hours (2024 only) by technical accounts (<0.01% X following):
-
-                PLAN:
-                {}
-
-                Find posts from last 72 hours about:
-                1. Technical developments directly related to plan components
-                2. New tools/methods that could enhance plan execution
-                3. Similar approaches being tested by research labs
-                4. Technical limitations discovered by researchers
-                
-                Requirements:
-                - Posts must be from last 72 hours in 2024
-                - Only from accounts with <0.01% X following
-                - Include exact timestamps and account names
-                - Link to any referenced papers/code
-                - Technical content only, no general news"#,
-                best_plan_narrative
-            );
+            let news_query = render_prompt("plan_news_query", &[("plan", &best_plan_narrative)])?;
 
             match self.api_client.query_llm(&news_query).await {
                 Ok(news) => println!("
@@ -632,46 +806,446 @@ Relevant developments:
             }
         }
 
-        // Create and save plan analysis
-        let all_plans: Vec<Plan> = updates.iter()
-            .filter_map(|cell| cell.current_plan.clone())
-            .collect();
-            
-        let analysis = PlanAnalysis::analyze_plans(&all_plans, cycle_id);
-        analysis.save_to_file(plans_path)?;
-
         // Update cells
         for cell in updates {
             self.cells.insert(cell.id, cell);
         }
+        for (cell_id, calls) in planners {
+            self.charge_llm_calls(&[cell_id], calls);
+        }
 
         Ok(())
     }
+
+    /// Merges similar proposals and lets their cells vote on the result. Each
+    /// cluster of similar plans becomes one candidate, synthesised by the model,
+    /// and every proposer votes for the candidate whose cells' Thought DNA is
+    /// closest to its own. A candidate with a quorum becomes the master plan for
+    /// all voters; otherwise each candidate goes to the cells that voted for it.
+    pub async fn reach_consensus(&mut self, cycle_id: &str) -> Result<(), Box<dyn Error>> {
+        let mut proposals: Vec<Plan> = Vec::new();
+        for plan in self.cells.values().filter_map(|cell| cell.current_plan.as_ref()) {
+            if matches!(plan.status, PlanStatus::Proposed) && !proposals.iter().any(|p| p.id == plan.id) {
+                proposals.push(plan.clone());
+            }
+        }
+        if !self.consensus || proposals.len() < 2 {
+            return Ok(());
+        }
+        proposals.sort_by_key(|plan| plan.created_at);
+
+        let texts: Vec<String> = proposals.iter().map(plan_text).collect();
+        let embeddings = match self.embedding_client.as_deref() {
+            Some(client) => client.embed(&texts).await.ok(),
+            None => None,
+        };
+        let threshold = if embeddings.is_some() { PLAN_MERGE_EMBEDDING_SIMILARITY } else { PLAN_MERGE_SHINGLE_SIMILARITY };
+        let clusters = cluster_plans(&texts, embeddings.as_deref(), threshold);
+
+        let mut candidates = Vec::new();
+        for cluster in &clusters {
+            let plans: Vec<&Plan> = cluster.iter().map(|&i| &proposals[i]).collect();
+            if plans.len() == 1 {
+                candidates.push(plans[0].clone());
+                continue;
+            }
+            candidates.push(self.synthesize_candidate(&plans).await);
+        }
+
+        let axes = |id: &Uuid| self.cells.get(id).map(|cell| cell.dimensional_position.axes());
+        let positions: Vec<[f64; 6]> = candidates.iter()
+            .map(|plan| centroid(&plan.participating_cells.iter().filter_map(axes).collect::<Vec<_>>()))
+            .collect();
+        let mut ballots: Vec<(Uuid, Ballot)> = Vec::new();
+        for id in proposals.iter().flat_map(|plan| &plan.participating_cells) {
+            if ballots.iter().any(|(voter, _)| voter == id) {
+                continue;
+            }
+            if let Some(ballot) = axes(id).and_then(|position| vote(&position, &positions)) {
+                ballots.push((*id, ballot));
+            }
+        }
+        let Some((winner, share)) = tally(&ballots.iter().map(|(_, ballot)| *ballot).collect::<Vec<_>>(), candidates.len()) else {
+            return Ok(());
+        };
+
+        let mut adopted = Vec::new();
+        if share >= CONSENSUS_QUORUM {
+            let mut master = candidates.swap_remove(winner);
+            if clusters[winner].len() == 1 {
+                // A lone winner is synthesised and scored the same way as a merged one
+                master = self.synthesize_candidate(&[&master]).await;
+            }
+            master.participating_cells = ballots.iter().map(|(id, _)| *id).collect();
+            self.master_plan = Some(master.id);
+            adopted.push(master);
+        } else {
+            for (index, mut plan) in candidates.into_iter().enumerate() {
+                plan.participating_cells = ballots.iter()
+                    .filter(|(_, ballot)| ballot.choice == index)
+                    .map(|(id, _)| *id)
+                    .collect();
+                if !plan.participating_cells.is_empty() {
+                    adopted.push(plan);
+                }
+            }
+        }
+
+        let plans_path = Path::new("data/plans");
+        for plan in &adopted {
+            for id in &plan.participating_cells {
+                if let Some(cell) = self.cells.get_mut(id) {
+                    cell.current_plan = Some(plan.clone());
+                }
+            }
+            save_plan_to_file(plan, plans_path, cycle_id)?;
+        }
+
+        println!("║   Consensus: {} proposals in {} clusters, top plan won {:.0}% of {} votes{}",
+            proposals.len(), clusters.len(), share * 100.0, ballots.len(),
+            if share >= CONSENSUS_QUORUM { " and became the master plan" } else { "; no quorum" });
+        Ok(())
+    }
+
+    /// One plan from a cluster of similar proposals, synthesised by the model.
+    /// Falls back to the highest scoring proposal if the synthesis fails.
+    /// Merges a cluster of plans into one candidate. The synthesis and any
+    /// repairs are paid for by the cells whose plans merge.
+    async fn synthesize_candidate(&mut self, plans: &[&Plan]) -> Plan {
+        let repairs_before = repair_stats().attempts;
+        let merged = self.merge_plans(plans).await;
+        self.charge_llm_calls(&merged.participating_cells, 1 + repair_stats().attempts.saturating_sub(repairs_before));
+        merged
+    }
+
+    async fn merge_plans(&self, plans: &[&Plan]) -> Plan {
+        let mut thoughts: Vec<Thought> = Vec::new();
+        for thought in plans.iter().flat_map(|plan| &plan.thoughts) {
+            if !thoughts.iter().any(|t| t.id == thought.id) {
+                thoughts.push(thought.clone());
+            }
+        }
+        thoughts.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+        thoughts.truncate(PLAN_MERGE_MAX_THOUGHTS);
+
+        let mut participants: Vec<Uuid> = Vec::new();
+        for id in plans.iter().flat_map(|plan| &plan.participating_cells) {
+            if !participants.contains(id) {
+                participants.push(*id);
+            }
+        }
+
+        let proposals = plans.iter()
+            .enumerate()
+            .map(|(i, plan)| format!("PLAN {}:\n{}", i + 1, to_components(plan)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut merged = match self.synthesize_plan(&proposals).await {
+            Ok((summary, nodes)) if !nodes.is_empty() => Plan {
+                id: Uuid::new_v4(),
+                score: thoughts.iter().map(|t| t.relevance_score).sum::<f64>() / thoughts.len().max(1) as f64,
+                thoughts,
+                nodes,
+                summary,
+                participating_cells: Vec::new(),
+                created_at: Utc::now(),
+                status: PlanStatus::Proposed,
+            },
+            result => {
+                if let Err(e) = result {
+                    eprintln!("Error merging plans: {}", e);
+                }
+                plans.iter().max_by(|a, b| a.score.total_cmp(&b.score)).map(|plan| (*plan).clone()).unwrap_or_else(|| plans[0].clone())
+            }
+        };
+        merged.participating_cells = participants;
+        merged
+    }
+
+    async fn synthesize_plan(&self, proposals: &str) -> Result<(String, Vec<PlanNode>), Box<dyn Error>> {
+        let prompt = render_prompt("plan_merge", &[("mission", &self.mission), ("plans", proposals)])?;
+        let response = self.api_client.query_llm(&prompt).await?;
+        let response = validate_with_repair(&*self.api_client, response, "plan_format", validate_plan).await?;
+        let summary = response.lines()
+            .find_map(|line| line.trim().strip_prefix("SUMMARY:"))
+            .map(|summary| summary.trim().to_string())
+            .unwrap_or_default();
+        Ok((summary, parse_plan_nodes(&response)?))
+    }
+
+    fn is_executing(&self, cell_id: Uuid) -> bool {
+        self.cells.get(&cell_id)
+            .and_then(|cell| cell.current_plan.as_ref())
+            .is_some_and(Plan::is_active)
+    }
+
+    /// Works on every active plan: ready steps advance in dependency order at
+    /// a pace set by the participants' energy, finished steps are judged by the
+    /// plan evaluator, and plans complete or fail once their steps settle.
+    /// Participants are rewarded for each passed step and for a completed plan.
+    /// The cycle's plan analysis covers the plans worked on this cycle along
+    /// with the colony's running totals.
+    pub async fn execute_plans(&mut self, cycle_id: &str) -> Result<(), Box<dyn Error>> {
+        let mut plans: Vec<Plan> = Vec::new();
+        for plan in self.cells.values().filter_map(|cell| cell.current_plan.as_ref()) {
+            if plan.is_active() && !plans.iter().any(|p| p.id == plan.id) {
+                plans.push(plan.clone());
+            }
+        }
+
+        let mut finished_plans = Vec::new();
+        let mut worked_plans = Vec::new();
+        for mut plan in plans {
+            let participants: Vec<Uuid> = plan.participating_cells.iter()
+                .copied()
+                .filter(|id| self.cells.get(id).is_some_and(|cell| !cell.is_dormant()))
+                .collect();
+            let mean_energy = participants.iter()
+                .filter_map(|id| self.cells.get(id))
+                .map(|cell| cell.energy)
+                .sum::<f64>() / participants.len().max(1) as f64;
+
+            for index in advance(&mut plan, PLAN_PROGRESS_PER_CYCLE * mean_energy / 100.0) {
+                let passed = self.judge_step(&plan, index, mean_energy).await;
+                plan.nodes[index].status = if passed { PlanNodeStatus::Completed } else { PlanNodeStatus::Failed };
+                if passed {
+                    self.reward(&participants, PLAN_STEP_ENERGY_REWARD, PLAN_STEP_DOPAMINE_REWARD);
+                }
+                println!("║   Plan {} step '{}' {}", &plan.id.to_string()[..8], plan.nodes[index].title,
+                    if passed { "completed" } else { "failed" });
+            }
+            if participants.is_empty() {
+                plan.status = PlanStatus::Failed;
+            }
+            settle(&mut plan);
+
+            match plan.status {
+                PlanStatus::Completed => {
+                    self.plans_completed += 1;
+                    self.reward(&participants, PLAN_COMPLETION_ENERGY_REWARD, PLAN_COMPLETION_DOPAMINE_REWARD);
+                    finished_plans.push(plan.clone());
+                }
+                PlanStatus::Failed => {
+                    self.plans_failed += 1;
+                    self.reward(&participants, 0.0, -PLAN_FAILURE_DOPAMINE_PENALTY);
+                    finished_plans.push(plan.clone());
+                }
+                _ => {}
+            }
+
+            for cell in self.cells.values_mut() {
+                if cell.current_plan.as_ref().is_some_and(|p| p.id == plan.id) {
+                    cell.current_plan = Some(plan.clone());
+                }
+            }
+            worked_plans.push(plan);
+        }
+
+        let plans_path = Path::new("data/plans");
+        for plan in &finished_plans {
+            save_plan_to_file(plan, plans_path, cycle_id)?;
+        }
+        PlanAnalysis::analyze_plans(&worked_plans, cycle_id)
+            .with_overall(self.plans_completed, self.plans_failed)
+            .save_to_file(plans_path)?;
+
+        println!("║   Plan Execution: {} finished this cycle, {} completed / {} failed overall",
+            finished_plans.len(), self.plans_completed, self.plans_failed);
+        Ok(())
+    }
+
+    async fn judge_step(&self, plan: &Plan, index: usize, mean_energy: f64) -> bool {
+        let node = &plan.nodes[index];
+        if self.plan_evaluator == PlanEvaluator::Llm {
+            let completed = plan.nodes.iter()
+                .filter(|n| matches!(n.status, PlanNodeStatus::Completed))
+                .map(|n| format!("- {}", n.title))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let verdict = match render_prompt("plan_step_review", &[
+                ("mission", &self.mission),
+                ("summary", &plan.summary),
+                ("completed", if completed.is_empty() { "None" } else { &completed }),
+                ("title", &node.title),
+                ("description", &node.description),
+            ]) {
+                Ok(prompt) => self.api_client.query_llm(&prompt).await.ok().and_then(|r| parse_verdict(&r)),
+                Err(e) => {
+                    eprintln!("Error rendering plan step review: {}", e);
+                    None
+                }
+            };
+            if let Some(passed) = verdict {
+                return passed;
+            }
+        }
+        rand::random::<f64>() < rule_pass_chance(plan, node, mean_energy)
+    }
+
+    fn reward(&mut self, cell_ids: &[Uuid], energy: f64, dopamine: f64) {
+        for id in cell_ids {
+            if let Some(cell) = self.cells.get_mut(id) {
+                cell.energy = (cell.energy + energy).clamp(0.0, 100.0);
+                cell.dopamine = (cell.dopamine + dopamine).clamp(0.0, 1.0);
+            }
+        }
+    }
+
     pub fn add_cell(&mut self, position: Coordinates) -> Uuid {
-        let mut cell = Cell::new(position.clone());
+        self.insert_cell(Cell::new(position))
+    }
+
+    fn insert_cell(&mut self, cell: Cell) -> Uuid {
+        self.lineage.record_birth(cell.id, cell.parent_id, cell.co_parent_id, &cell.dimensional_position, self.cycle);
+        self.place_cell(cell)
+    }
+
+    // Adds a cell the lineage already knows about
+    fn place_cell(&mut self, cell: Cell) -> Uuid {
         let id = cell.id;
+        self.cell_positions.insert(id, cell.position.clone());
+        self.spatial.insert(id, &cell.position);
         self.cells.insert(id, cell);
-        self.cell_positions.insert(id, position);
         self.update_neighbors(id);
         id
     }
 
+    /// Cells within `radius` of `position`, nearest first.
+    pub fn cells_within(&self, position: &Coordinates, radius: f64) -> Vec<(Uuid, f64)> {
+        self.spatial.within(position, radius)
+    }
+
+    /// Recomputes `cell_id`'s neighbours and updates the lists on the other
+    /// side of every link that was gained or lost, keeping the graph symmetric.
     fn update_neighbors(&mut self, cell_id: Uuid) {
-        let cell_pos = self.cells.get(&cell_id).unwrap().position.clone();
-        let mut neighbors = Vec::new();
-
-        for (other_id, other_cell) in self.cells.iter() {
-            if *other_id != cell_id {
-                let distance = calculate_distance(&cell_pos, &other_cell.position);
-                if distance < NEIGHBOR_DISTANCE_THRESHOLD {
-                    neighbors.push(*other_id);
+        let Some(cell_pos) = self.cell_positions.get(&cell_id).cloned() else { return };
+        let neighbors: Vec<Uuid> = self.cells_within(&cell_pos, NEIGHBOR_DISTANCE_THRESHOLD)
+            .into_iter()
+            .filter(|(other_id, distance)| *other_id != cell_id && *distance < NEIGHBOR_DISTANCE_THRESHOLD)
+            .map(|(other_id, _)| other_id)
+            .collect();
+
+        let previous = match self.cells.get_mut(&cell_id) {
+            Some(cell) => std::mem::replace(&mut cell.neighbors, neighbors.clone()),
+            None => return,
+        };
+        for lost in previous.iter().filter(|id| !neighbors.contains(id)) {
+            if let Some(other) = self.cells.get_mut(lost) {
+                other.neighbors.retain(|neighbor| *neighbor != cell_id);
+            }
+        }
+        for gained in neighbors.iter().filter(|id| !previous.contains(id)) {
+            if let Some(other) = self.cells.get_mut(gained) {
+                if !other.neighbors.contains(&cell_id) {
+                    other.neighbors.push(cell_id);
                 }
             }
         }
+    }
 
-        if let Some(cell) = self.cells.get_mut(&cell_id) {
-            cell.neighbors = neighbors;
+    /// Sends a message from `from` to one cell, its neighbours or the whole
+    /// colony. Returns how many cells it reached.
+    pub fn post(&mut self, from: Uuid, to: Recipients, kind: MessageKind, content: String, thought_id: Option<String>) -> usize {
+        let recipients: Vec<Uuid> = match to {
+            Recipients::Cell(id) => vec![id],
+            Recipients::Neighbors => self.cells.get(&from).map(|cell| cell.neighbors.clone()).unwrap_or_default(),
+            Recipients::Broadcast => self.cells.keys().copied().collect(),
+        };
+        let message = Message { from, kind, thought_id, content, sent_at: Utc::now() };
+
+        let mut reached = 0;
+        for id in recipients.into_iter().filter(|id| *id != from && self.cells.contains_key(id)) {
+            self.messages.deliver(id, message.clone());
+            reached += 1;
         }
+        reached
+    }
+
+    /// Keeps only the references in a newly stored thought that point at
+    /// thoughts other cells still hold, logs each as an exchange on both sides
+    /// and sends the cited cell the thought that built on its work. The thought
+    /// then goes to the cell's neighbours, and each `REQUEST:` line to its
+    /// neighbours or, for an isolated cell, the whole colony.
+    fn publish_thought(&mut self, cell_id: Uuid, thought_id: &str) {
+        let Some(thought) = self.cells.get(&cell_id)
+            .and_then(|cell| cell.thoughts.iter().find(|t| t.id == thought_id))
+            .cloned() else { return };
+
+        let references: Vec<(Uuid, String)> = thought.referenced_thoughts.iter()
+            .filter(|(peer, id)| *peer != cell_id && self.cells.get(peer).is_some_and(|p| p.thoughts.iter().any(|t| &t.id == id)))
+            .cloned()
+            .collect();
+        if references.len() < thought.referenced_thoughts.len() {
+            log_warning(&format!("Cell {} cited {} thought(s) that don't exist; dropped",
+                cell_id, thought.referenced_thoughts.len() - references.len()));
+        }
+        if let Some(stored) = self.cells.get_mut(&cell_id).and_then(|cell| cell.thoughts.iter_mut().find(|t| t.id == thought_id)) {
+            stored.referenced_thoughts = references.clone();
+        }
+
+        let neighbors = self.cells.get(&cell_id).map(|cell| cell.neighbors.clone()).unwrap_or_default();
+        for (peer, cited) in &references {
+            if let Some(cell) = self.cells.get_mut(&cell_id) {
+                cell.record_collaboration(*peer, format!("Built on thought {} from {}", cited, &peer.to_string()[..8]));
+            }
+            if let Some(other) = self.cells.get_mut(peer) {
+                other.record_collaboration(cell_id, format!("Thought {} cited by {}", cited, &cell_id.to_string()[..8]));
+            }
+            if !neighbors.contains(peer) {
+                self.post(cell_id, Recipients::Cell(*peer), MessageKind::Thought, thought.content.clone(), Some(thought.id.clone()));
+            }
+        }
+
+        self.post(cell_id, Recipients::Neighbors, MessageKind::Thought, thought.content.clone(), Some(thought.id.clone()));
+        let audience = if neighbors.is_empty() { Recipients::Broadcast } else { Recipients::Neighbors };
+        for request in parse_requests(&thought.content) {
+            self.post(cell_id, audience, MessageKind::Request, request, None);
+        }
+    }
+
+    /// Moves a cell and relinks it with the cells around its new position.
+    pub fn move_cell(&mut self, cell_id: Uuid, position: Coordinates) {
+        let Some(cell) = self.cells.get_mut(&cell_id) else { return };
+        cell.position = position.clone();
+        self.spatial.insert(cell_id, &position);
+        self.cell_positions.insert(cell_id, position);
+        self.update_neighbors(cell_id);
+    }
+
+    /// Takes a cell out of the colony and out of its neighbours' lists.
+    fn remove_cell(&mut self, cell_id: Uuid) -> Option<Cell> {
+        let cell = self.cells.remove(&cell_id)?;
+        self.cell_positions.remove(&cell_id);
+        self.spatial.remove(cell_id);
+        self.messages.drop_inbox(cell_id);
+        for neighbor_id in &cell.neighbors {
+            if let Some(other) = self.cells.get_mut(neighbor_id) {
+                other.neighbors.retain(|neighbor| *neighbor != cell_id);
+            }
+        }
+        Some(cell)
+    }
+
+    /// Checks that every neighbour link exists in both directions and matches
+    /// the spatial index. Run after each phase in debug builds.
+    fn check_neighbor_graph(&self) -> Result<(), String> {
+        for (id, cell) in &self.cells {
+            let mut expected: Vec<Uuid> = self.cells_within(&cell.position, NEIGHBOR_DISTANCE_THRESHOLD)
+                .into_iter()
+                .filter(|(other_id, distance)| other_id != id && *distance < NEIGHBOR_DISTANCE_THRESHOLD)
+                .map(|(other_id, _)| other_id)
+                .collect();
+            let mut actual = cell.neighbors.clone();
+            expected.sort();
+            actual.sort();
+            if actual != expected {
+                return Err(format!("cell {} has {} neighbours, expected {}", id, actual.len(), expected.len()));
+            }
+            if let Some(other) = actual.iter().find(|other| !self.cells[*other].neighbors.contains(id)) {
+                return Err(format!("cell {} lists {} as a neighbour but not the reverse", id, other));
+            }
+        }
+        Ok(())
     }
 
     pub async fn handle_cell_reproduction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
@@ -679,42 +1253,79 @@ Relevant developments:
 [{}] Starting cell reproduction cycle", 
             chrono::Local::now().format("%H:%M:%S"));
         let mut new_cells = Vec::new();
-        let mut rng = rand::thread_rng();
+        let mut crossovers = 0;
         
-        for cell in self.cells.values() {
-            if cell.energy > 90.0 && rng.gen::<f64>() < 0.1 {
-                let mut new_position = Coordinates {
-                    x: cell.position.x + (rng.gen::<f64>() - 0.5),
-                    y: cell.position.y + (rng.gen::<f64>() - 0.5),
-                    z: cell.position.z + (rng.gen::<f64>() - 0.5),
-                    heat: cell.position.heat * (0.9 + rng.gen::<f64>() * 0.2), // Inherit with slight variation
-                    emergence_score: cell.position.emergence_score + (rng.gen::<f64>() * 10.0 - 5.0),
-                    coherence_score: cell.position.coherence_score + (rng.gen::<f64>() * 10.0 - 5.0),
-                    resilience_score: cell.position.resilience_score + (rng.gen::<f64>() * 10.0 - 5.0),
-                    intelligence_score: cell.position.intelligence_score + (rng.gen::<f64>() * 10.0 - 5.0),
-                    efficiency_score: cell.position.efficiency_score + (rng.gen::<f64>() * 10.0 - 5.0),
-                    integration_score: cell.position.integration_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        // Budding is decided by the LTL phase: only cells that met the birth rule
+        // reproduce, and only while the colony is below its carrying capacity
+        let room = self.carrying_capacity.saturating_sub(self.cells.len());
+        let mut spawns = std::mem::take(&mut self.pending_spawns);
+        if spawns.len() > room {
+            println!("[{}] Carrying capacity {} reached; {} of {} buds suppressed",
+                chrono::Local::now().format("%H:%M:%S"),
+                self.carrying_capacity, spawns.len() - room, spawns.len());
+            spawns.truncate(room);
+        }
+
+        {
+            let mut rng = rand::thread_rng();
+            let mut paired = std::collections::HashSet::new();
+            for parent_id in spawns {
+                if paired.contains(&parent_id) {
+                    continue;
+                }
+                let partner_id = match self.reproduction_mode {
+                    ReproductionMode::Budding => None,
+                    _ => self.crossover_partner(parent_id, &paired),
                 };
-                
-                // Clamp all scores to valid ranges
-                new_position.heat = new_position.heat.clamp(0.0, 1.0);
-                new_position.emergence_score = new_position.emergence_score.clamp(-100.0, 100.0);
-                new_position.coherence_score = new_position.coherence_score.clamp(-100.0, 100.0);
-                new_position.resilience_score = new_position.resilience_score.clamp(-100.0, 100.0);
-                new_position.intelligence_score = new_position.intelligence_score.clamp(-100.0, 100.0);
-                new_position.efficiency_score = new_position.efficiency_score.clamp(-100.0, 100.0);
-                new_position.integration_score = new_position.integration_score.clamp(-100.0, 100.0);
-                new_cells.push(new_position);
+                if partner_id.is_none() && self.reproduction_mode == ReproductionMode::Crossover {
+                    continue;
+                }
+
+                let Some(cell) = self.cells.get(&parent_id) else { continue };
+                let new_position = offspring_position(&cell.position, &mut rng);
+                let mut child = match partner_id.and_then(|id| self.cells.get(&id)) {
+                    Some(partner) => cell.crossover(partner, new_position, &mut rng),
+                    None => cell.offspring(new_position, &mut rng),
+                };
+
+                // Each parent hands a share of its energy to the child
+                let mut energy = 0.0;
+                for id in std::iter::once(parent_id).chain(partner_id) {
+                    if let Some(parent) = self.cells.get_mut(&id) {
+                        let share = parent.energy * LTL_OFFSPRING_ENERGY_SHARE;
+                        parent.energy -= share;
+                        parent.enhanced_state.energy = parent.energy;
+                        energy += share;
+                    }
+                }
+                child.energy = energy;
+                child.enhanced_state.energy = energy;
+
+                if let Some(partner_id) = partner_id {
+                    paired.insert(parent_id);
+                    paired.insert(partner_id);
+                    crossovers += 1;
+                }
+                new_cells.push(child);
             }
         }
 
-        println!("[{}] Creating {} new cells through reproduction", 
+        if self.crossover_llm_merge {
+            for child in new_cells.iter_mut() {
+                if let Err(e) = self.merge_parent_memories(child).await {
+                    eprintln!("Error merging parent memories for cell {}: {}", child.id, e);
+                }
+            }
+        }
+
+        println!("[{}] Creating {} new cells through reproduction ({} by crossover)", 
             chrono::Local::now().format("%H:%M:%S"),
-            new_cells.len());
+            new_cells.len(), crossovers);
         
-        for position in new_cells {
-            self.add_cell(position);
+        for child in new_cells {
+            self.insert_cell(child);
         }
+        debug_assert_eq!(self.check_neighbor_graph(), Ok(()));
         
         println!("[{}] Cell reproduction cycle completed", 
             chrono::Local::now().format("%H:%M:%S"));
@@ -722,6 +1333,38 @@ Relevant developments:
         Ok(())
     }
 
+    // The awake, energetic neighbour that best balances `cell_id`'s dimensions
+    fn crossover_partner(&self, cell_id: Uuid, taken: &std::collections::HashSet<Uuid>) -> Option<Uuid> {
+        let cell = self.cells.get(&cell_id)?;
+        cell.neighbors.iter()
+            .filter(|id| **id != cell_id && !taken.contains(*id))
+            .filter_map(|id| self.cells.get(id).map(|neighbor| (*id, neighbor)))
+            .filter(|(_, neighbor)| !neighbor.is_dormant() && neighbor.energy >= CROSSOVER_MIN_ENERGY)
+            .map(|(id, neighbor)| (id, calculate_dimensional_complement(&cell.dimensional_position, &neighbor.dimensional_position)))
+            .filter(|(_, complement)| *complement >= CROSSOVER_MIN_COMPLEMENT)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    // Replaces a crossover child's interleaved memories with one summary of both parents'
+    async fn merge_parent_memories(&self, child: &mut Cell) -> Result<(), Box<dyn Error>> {
+        let memories = |id: Option<Uuid>| {
+            id.and_then(|id| self.cells.get(&id))
+                .map(|parent| parent.compressed_memories.join("\n"))
+                .unwrap_or_default()
+        };
+        let (first, second) = (memories(child.parent_id), memories(child.co_parent_id));
+        if child.co_parent_id.is_none() || first.is_empty() || second.is_empty() {
+            return Ok(());
+        }
+
+        let prompt = render_prompt("memory_merge", &[("first", &first), ("second", &second)])?;
+        let merged = self.api_client.query_llm(&prompt).await?;
+        child.compressed_memories = vec![merged.trim().to_string()];
+        child.spend_energy(ENERGY_COST_PER_LLM_CALL);
+        Ok(())
+    }
+
     pub async fn update_mission_progress(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Implementation for mission progress update
         Ok(())
@@ -745,8 +1388,10 @@ Relevant developments:
         let mut total_thoughts = 0;
         let mut total_compressed_memories = 0;
         let mut cells_with_plans = 0;
+        let mut duplicate_thoughts = 0;
         
         for cell in self.cells.values() {
+            duplicate_thoughts += cell.duplicate_thoughts;
             total_energy += cell.energy;
             total_thoughts += cell.thoughts.len();
             total_compressed_memories += cell.compressed_memories.len();
@@ -763,6 +1408,39 @@ Relevant developments:
                 println!("║ │ Total Thoughts           │ {:<21} │ ║", total_thoughts);
         println!("║ │ Cells with Active Plans  │ {:<21} │ ║", cells_with_plans);
         println!("║ │ Compressed Memory Blocks │ {:<21} │ ║", total_compressed_memories);
+        println!("║ │ Average Thought Novelty  │ {:<21.2} │ ║", self.get_average_novelty());
+        println!("║ │ Phase Synchronization    │ {:<21.2} │ ║", self.get_phase_order());
+        println!("║ │ Population / Capacity    │ {:<21} │ ║", format!("{} / {}", self.cells.len(), self.carrying_capacity));
+        println!("║ │ Dormant Cells            │ {:<21} │ ║", self.get_dormant_count());
+        println!("║ │ Deaths                   │ {:<21} │ ║", self.deaths);
+        let generation = self.cells.keys()
+            .filter_map(|id| self.lineage.get(id))
+            .map(|record| record.generation)
+            .max()
+            .unwrap_or(0);
+        println!("║ │ Latest Generation        │ {:<21} │ ║", generation);
+        if let Some((founder, score)) = self.lineage.most_successful_lineage() {
+            println!("║ │ Top Lineage (Plan Score) │ {:<21} │ ║", format!("{} ({:.2})", &founder.to_string()[..8], score));
+        }
+        println!("║ │ Energy Spent             │ {:<21.1} │ ║", self.get_energy_spent());
+        println!("║ │ Duplicate Thoughts       │ {:<21} │ ║", duplicate_thoughts);
+        println!("║ │ Plans Completed / Failed │ {:<21} │ ║", format!("{} / {}", self.plans_completed, self.plans_failed));
+        println!("║ │ Messages Sent / Pending  │ {:<21} │ ║", format!("{} / {}", self.messages.delivered(), self.messages.pending()));
+        if let Some(master) = self.master_plan {
+            let followers = self.cells.values()
+                .filter(|cell| cell.current_plan.as_ref().is_some_and(|plan| plan.id == master && plan.is_active()))
+                .count();
+            println!("║ │ Master Plan Followers    │ {:<21} │ ║", followers);
+        }
+        if let Some(cache_stats) = self.api_client.cache_stats() {
+            println!("║ │ LLM Cache Hits / Misses  │ {:<21} │ ║", format!("{} / {}", cache_stats.hits, cache_stats.misses));
+            println!("║ │ LLM Cache Hit Rate       │ {:<21} │ ║", format!("{:.1}%", cache_stats.hit_rate() * 100.0));
+        }
+        let repairs = repair_stats();
+        if repairs.malformed > 0 {
+            println!("║ │ Malformed / Repaired     │ {:<21} │ ║", format!("{} / {}", repairs.malformed, repairs.repaired));
+            println!("║ │ Repair Success Rate      │ {:<21} │ ║", format!("{:.1}%", repairs.success_rate() * 100.0));
+        }
         println!("║ └──────────────────────────┴───────────────────────────┘ ║");
         println!("╚════════════════════════════════════════════════════════════╝");
     }
@@ -807,6 +1485,7 @@ Relevant developments:
         use std::sync::Arc;
         use futures::future::join_all;
         
+        self.cycle += 1;
         log_timestamp("Starting cell evolution cycle");
         
         let evolution_id = Uuid::new_v4();
@@ -829,6 +1508,11 @@ Relevant developments:
         println!("║   Total Energy: {:.2}", 
             self.cells.values().map(|c| c.energy).sum::<f64>());
         
+        self.step_lenia_field();
+        println!("║   Lenia Field Mass: {:.2}", self.lenia_world.grid().sum());
+        self.run_ltl_phase().await;
+        self.run_movement_phase();
+
         // Simple position-based evolution
         for (id, pos) in self.cell_positions.iter_mut() {
             if let Some(cell) = self.cells.get_mut(id) {
@@ -850,6 +1534,10 @@ Relevant developments:
         println!("                      Evolution Batch                          ");
         println!("--------------------------------------------------------------");
 
+        // Resources regrow logistically: plentiful in a small colony, none at capacity
+        let regen = RESOURCE_REGEN_PER_CYCLE
+            * (1.0 - self.cells.len() as f64 / self.carrying_capacity as f64).max(0.0);
+
         // Create a semaphore to limit concurrent tasks
         let semaphore = Arc::new(Semaphore::new(4)); // Limit to 4 concurrent batches
         let mut tasks = Vec::new();
@@ -882,9 +1570,10 @@ Relevant developments:
                     let lenia_influence = lenia_state * cell.lenia_influence;
                     cell.energy = (cell.energy + lenia_influence).clamp(0.0, 100.0);
                     
-                    // Base energy regeneration
-                    if cell.energy < 50.0 {
-                        cell.energy += 10.0;
+                    // Resource regeneration, then the cost of staying awake
+                    cell.energy = (cell.energy + regen).min(100.0);
+                    if !cell.is_dormant() {
+                        cell.spend_energy(METABOLIC_COST_PER_CYCLE);
                     }
                     
                     // Update cell stability based on Lenia state
@@ -916,6 +1605,9 @@ Relevant developments:
             }
         }
 
+        self.update_dormancy();
+        debug_assert_eq!(self.check_neighbor_graph(), Ok(()));
+
         println!("
 [{}] Evolution cycle completed for {} cells", 
             chrono::Local::now().format("%H:%M:%S"),
@@ -927,40 +1619,326 @@ Relevant developments:
         Ok(())
     }
 
+    /// Cells out of energy go dormant. A dormant cell wakes once it regains
+    /// `DORMANCY_WAKE_ENERGY`, or dies after `DORMANCY_MAX_CYCLES`, leaving
+    /// its memories in the archive.
+    fn update_dormancy(&mut self) {
+        let mut dead = Vec::new();
+        for (id, cell) in self.cells.iter_mut() {
+            match cell.dormant_cycles {
+                Some(_) if cell.energy >= DORMANCY_WAKE_ENERGY => cell.dormant_cycles = None,
+                Some(cycles) if cycles + 1 >= DORMANCY_MAX_CYCLES => dead.push(*id),
+                Some(cycles) => cell.dormant_cycles = Some(cycles + 1),
+                None if cell.energy <= 0.0 => cell.dormant_cycles = Some(0),
+                None => {}
+            }
+        }
+
+        for id in dead {
+            let Some(cell) = self.remove_cell(id) else { continue };
+
+            let archive = CellArchive {
+                id,
+                died_at: Utc::now(),
+                energy_spent: cell.energy_spent,
+                thoughts: cell.thoughts.into_iter().collect(),
+                compressed_memories: cell.compressed_memories,
+                current_plan: cell.current_plan,
+                research_topics: cell.research_topics,
+                dimensional_position: cell.dimensional_position,
+            };
+            if let Err(e) = archive.save_to_dir(Path::new(CELL_ARCHIVE_DIR)) {
+                eprintln!("Error archiving cell {}: {}", id, e);
+            }
+            self.archived_energy_spent += cell.energy_spent;
+            self.deaths += 1;
+            self.lineage.record_death(id, self.cycle);
+            println!("║   Cell {} died after {} dormant cycles", id, DORMANCY_MAX_CYCLES);
+        }
+    }
+
+    pub fn get_dormant_count(&self) -> usize {
+        self.cells.values().filter(|cell| cell.is_dormant()).count()
+    }
+
+    pub fn get_energy_spent(&self) -> f64 {
+        self.archived_energy_spent + self.cells.values().map(|cell| cell.energy_spent).sum::<f64>()
+    }
+
+    /// Larger-than-Life phase. Every cell updates against its neighbours' states
+    /// as they were at the start of the phase, so the order cells are visited
+    /// in doesn't matter. Energy transfers are then settled between cells,
+    /// cells meeting the birth rule are queued to bud, and cells the rules mark
+    /// as ready think through the colony's model client.
+    async fn run_ltl_phase(&mut self) {
+        let states: HashMap<Uuid, EnhancedCellState> = self.cells.iter()
+            .map(|(id, cell)| (*id, cell.ltl_state()))
+            .collect();
+
+        let mut ready = Vec::new();
+        let mut transfers = Vec::new();
+        let mut starving = 0;
+        self.pending_spawns.clear();
+        for (id, cell) in self.cells.iter_mut() {
+            if cell.is_dormant() {
+                continue;
+            }
+            // Only cells inside the neighbourhood radius can influence this one
+            let nearby: Vec<(Uuid, Coordinates)> = self.spatial.within(&cell.position, cell.neighborhood.radius)
+                .into_iter()
+                .filter_map(|(other, _)| self.cell_positions.get(&other).map(|position| (other, position.clone())))
+                .collect();
+            for effect in cell.apply_ltl_rules(&nearby, &states, &self.ltl_rules) {
+                match effect {
+                    InteractionEffect::EnergyTransfer { from, amount } => transfers.push((from, *id, amount)),
+                    InteractionEffect::SpawnConditionsMet => self.pending_spawns.push(*id),
+                    InteractionEffect::SurvivalFailed => starving += 1,
+                    InteractionEffect::SynchronizationBonus(_) => {}
+                }
+            }
+            if cell.should_generate_thought() {
+                ready.push(*id);
+            }
+        }
+
+        for (from, to, amount) in transfers {
+            self.transfer_energy(from, to, amount);
+        }
+
+        println!("║   LTL Phase: {} cells updated, {} ready to think, {} ready to bud, {} outside survival range, order {:.2}",
+            states.len(), ready.len(), self.pending_spawns.len(), starving, self.get_phase_order());
+
+        for id in ready {
+            if let Err(e) = self.process_cell_thoughts(id).await {
+                eprintln!("Error generating LTL thought for cell {}: {}", id, e);
+            }
+        }
+    }
+
+    /// Splits the energy cost of `calls` model calls evenly between `cell_ids`.
+    fn charge_llm_calls(&mut self, cell_ids: &[Uuid], calls: u64) {
+        if cell_ids.is_empty() || calls == 0 {
+            return;
+        }
+        let share = ENERGY_COST_PER_LLM_CALL * calls as f64 / cell_ids.len() as f64;
+        for id in cell_ids {
+            if let Some(cell) = self.cells.get_mut(id) {
+                cell.spend_energy(share);
+            }
+        }
+    }
+
+    /// Moves up to `amount` energy between two cells and returns what moved.
+    /// Transfers move energy rather than create or destroy it, so the amount is
+    /// capped by what the donor has left and by the recipient's headroom.
+    fn transfer_energy(&mut self, from: Uuid, to: Uuid, amount: f64) -> f64 {
+        let (Some(donor), Some(recipient)) = (self.cells.get(&from), self.cells.get(&to)) else {
+            return 0.0;
+        };
+        let amount = amount.min(donor.energy).min(100.0 - recipient.energy).max(0.0);
+        if let Some(donor) = self.cells.get_mut(&from) {
+            donor.energy -= amount;
+        }
+        if let Some(recipient) = self.cells.get_mut(&to) {
+            recipient.energy += amount;
+        }
+        amount
+    }
+
+    /// Every awake cell senses the cells around it and the Lenia field, then
+    /// moves according to the movement rules. Velocities are worked out from
+    /// the positions at the start of the phase, so the order of moves doesn't matter.
+    fn run_movement_phase(&mut self) {
+        if !self.movement.is_enabled() {
+            return;
+        }
+
+        // One grid step in world units, for a central-difference field gradient
+        let step = 1.0 / self.lenia_world.params.transform.scale;
+        let mut moves = Vec::new();
+        for (id, cell) in &self.cells {
+            if cell.is_dormant() {
+                continue;
+            }
+            let sensed: Vec<SensedNeighbor> = self.spatial.within(&cell.position, self.movement.sense_radius)
+                .into_iter()
+                .filter(|(other_id, _)| other_id != id)
+                .filter_map(|(other_id, distance)| {
+                    let other = self.cells.get(&other_id)?;
+                    Some(SensedNeighbor {
+                        offset: [
+                            other.position.x - cell.position.x,
+                            other.position.y - cell.position.y,
+                            other.position.z - cell.position.z,
+                        ],
+                        distance,
+                        complement: calculate_dimensional_complement(&cell.dimensional_position, &other.dimensional_position),
+                        energy: other.energy,
+                    })
+                })
+                .collect();
+
+            let gradient = [0, 1, 2].map(|axis| {
+                let mut ahead = cell.position.clone();
+                let mut behind = cell.position.clone();
+                let (a, b) = match axis {
+                    0 => (&mut ahead.x, &mut behind.x),
+                    1 => (&mut ahead.y, &mut behind.y),
+                    _ => (&mut ahead.z, &mut behind.z),
+                };
+                *a += step;
+                *b -= step;
+                (self.lenia_world.get_state_at(&ahead) - self.lenia_world.get_state_at(&behind)) / (2.0 * step)
+            });
+
+            let position = [cell.position.x, cell.position.y, cell.position.z];
+            let velocity = self.movement.velocity(position, &sensed, gradient);
+            let distance = velocity.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if distance > 1e-6 {
+                let mut position = cell.position.clone();
+                position.x += velocity[0];
+                position.y += velocity[1];
+                position.z += velocity[2];
+                moves.push((*id, position, distance));
+            }
+        }
+
+        let moved = moves.len();
+        let travelled: f64 = moves.iter().map(|(_, _, distance)| distance).sum();
+        for (id, position, distance) in moves {
+            self.move_cell(id, position);
+            if let Some(cell) = self.cells.get_mut(&id) {
+                cell.spend_energy(distance * self.movement.energy_cost);
+            }
+        }
+
+        println!("║   Movement: {} cells moved, mean step {:.2}, {} clusters",
+            moved, if moved > 0 { travelled / moved as f64 } else { 0.0 }, self.get_cluster_count());
+    }
+
+    /// Cells deposit their energy and recent thought activity into channel 0 of
+    /// the Lenia field and each dimension into its own channel. The field
+    /// advances, then each cell reads back its activity level and is pulled
+    /// toward the dimension values around it.
+    fn step_lenia_field(&mut self) {
+        self.fit_lenia_bounds();
+        let since = self.lenia_last_step;
+        let couple_dimensions = self.lenia_world.channels.len() > DIMENSION_MASS_CHANNEL;
+        if couple_dimensions {
+            for channel in &mut self.lenia_world.channels[1..=DIMENSION_MASS_CHANNEL] {
+                channel.mapv_inplace(|v| v * LENIA_DIMENSION_MEMORY);
+            }
+        }
+        for cell in self.cells.values() {
+            let new_thoughts = cell.thoughts.iter().filter(|t| t.timestamp > since).count();
+            let activity = (new_thoughts as f64 / 3.0).min(1.0);
+            let amplitude = LENIA_DEPOSIT_STRENGTH * (0.5 * cell.energy / 100.0 + 0.5 * activity);
+            if amplitude > 0.0 {
+                self.lenia_world.add_pattern(&blob_pattern(LENIA_DEPOSIT_RADIUS, amplitude), &cell.position);
+            }
+
+            if !couple_dimensions {
+                continue;
+            }
+            // Each axis channel carries mass times the normalised axis value, so
+            // dividing by the mass channel recovers the neighbours' mean value
+            let mass = blob_pattern(LENIA_DEPOSIT_RADIUS, 1.0);
+            self.lenia_world.add_pattern_to(DIMENSION_MASS_CHANNEL, &mass, &cell.position);
+            for (axis, value) in cell.dimensional_position.axes().iter().enumerate() {
+                let normalized = ((value + 100.0) / 200.0).clamp(0.0, 1.0);
+                self.lenia_world.add_pattern_to(axis + 1, &mass.mapv(|m| m * normalized), &cell.position);
+            }
+        }
+
+        for _ in 0..LENIA_STEPS_PER_CYCLE {
+            self.lenia_world.step();
+        }
+        self.lenia_last_step = Utc::now();
+
+        let kernel = dimension_kernel();
+        let smoothed: Vec<_> = if couple_dimensions {
+            (1..=DIMENSION_MASS_CHANNEL).map(|channel| self.lenia_world.convolve(channel, &kernel)).collect()
+        } else {
+            Vec::new()
+        };
+        for cell in self.cells.values_mut() {
+            cell.lenia_state = self.lenia_world.get_state_at(&cell.position);
+
+            let Some((mass, axes)) = smoothed.split_last() else { continue };
+            let mass = self.lenia_world.sample(mass, &cell.position);
+            if mass <= f64::EPSILON {
+                continue;
+            }
+            let position = cell.position.clone();
+            for (value, field) in cell.dimensional_position.axes_mut().into_iter().zip(axes) {
+                let mean = (self.lenia_world.sample(field, &position) / mass).clamp(0.0, 1.0) * 200.0 - 100.0;
+                *value = (*value + LENIA_DIMENSION_COUPLING * (mean - *value)).clamp(-100.0, 100.0);
+            }
+        }
+    }
+
+    // Grows the field's world-space box when cells drift outside it, so
+    // distant cells never wrap onto each other
+    fn fit_lenia_bounds(&mut self) {
+        let shape = self.lenia_world.params.shape();
+        let (mut min, mut max) = self.lenia_world.params.transform.bounds(shape);
+        let mut outside = false;
+        for cell in self.cells.values() {
+            let position = [cell.position.x, cell.position.y, cell.position.z];
+            for axis in 0..3 {
+                if shape[axis] == 1 {
+                    continue;
+                }
+                if position[axis] < min[axis] {
+                    min[axis] = position[axis] - LENIA_BOUNDS_MARGIN;
+                    outside = true;
+                } else if position[axis] > max[axis] {
+                    max[axis] = position[axis] + LENIA_BOUNDS_MARGIN;
+                    outside = true;
+                }
+            }
+        }
+
+        if outside {
+            self.lenia_world.retarget(GridTransform::fitting(min, max, shape));
+        }
+    }
+
     pub fn save_state(&self) -> Result<(), Box<dyn std::error::Error>> {
         self.save_state_to_file("eca_state.json")?;
+        self.lineage.export(Path::new(LINEAGE_DIR))?;
         Ok(())
     }
 
     pub fn save_state_to_file(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
         use crate::models::state::{ColonyState, CellState, EnergyGridState};
         
-        // Calculate grid size based on cell positions
-        let max_coord = self.cells.values()
-            .flat_map(|cell| vec![
-                (cell.position.x * 2.0) as usize,
-                (cell.position.y * 2.0) as usize,
-                (cell.position.z * 2.0) as usize
-            ])
-            .max()
-            .unwrap_or(0) + 1;
-        
-        let grid_size = max_coord + 1;
+        // Size the grid to the cells' bounding box, two grid steps per world unit
+        let to_grid = |cell: &Cell| [cell.position.x, cell.position.y, cell.position.z].map(|v| (v * 2.0).floor() as i64);
+        let mut low = [i64::MAX; 3];
+        let mut high = [i64::MIN; 3];
+        for cell in self.cells.values() {
+            for (axis, v) in to_grid(cell).into_iter().enumerate() {
+                low[axis] = low[axis].min(v);
+                high[axis] = high[axis].max(v);
+            }
+        }
+        if self.cells.is_empty() {
+            low = [0; 3];
+            high = [0; 3];
+        }
+
+        let grid_size = (0..3).map(|axis| (high[axis] - low[axis]) as usize + 1).max().unwrap_or(1);
         let total_size = grid_size * grid_size * grid_size;
         let mut grid = vec![0.0; total_size];
         let mut cell_positions = HashMap::new();
         
         // Map cells to grid positions and store energy values
         for (id, cell) in &self.cells {
-            let x = (cell.position.x * 2.0) as usize;
-            let y = (cell.position.y * 2.0) as usize;
-            let z = (cell.position.z * 2.0) as usize;
-            
-            let idx = z * grid_size * grid_size + y * grid_size + x;
-            if idx < total_size {
-                grid[idx] = cell.energy;
-                cell_positions.insert(*id, (x, y, z));
-            }
+            let [x, y, z] = to_grid(cell);
+            let (x, y, z) = ((x - low[0]) as usize, (y - low[1]) as usize, (z - low[2]) as usize);
+            grid[z * grid_size * grid_size + y * grid_size + x] = cell.energy;
+            cell_positions.insert(*id, (x, y, z));
         }
         
         let cell_states: HashMap<Uuid, CellState> = self.cells.iter()
@@ -981,6 +1959,8 @@ Relevant developments:
                     x: cell.position.x,
                     y: cell.position.y,
                     z: cell.position.z,
+                    parent_id: cell.parent_id,
+                    co_parent_id: cell.co_parent_id,
                 })
             })
             .collect();
@@ -988,14 +1968,16 @@ Relevant developments:
         let state = ColonyState {
             timestamp: Utc::now(),
             cells: cell_states,
-            total_cycles: 0,
+            total_cycles: self.cycle as u32,
             mission: self.mission.clone(),
-            lenia_world: None,
+            lenia_world: Some(self.lenia_world.to_state()),
             energy_grid: EnergyGridState {
                 size: grid_size,
+                origin: low.map(|v| v as f64 / 2.0),
                 grid,
                 cell_positions,
             },
+            lineage: self.lineage.clone(),
         };
 
         state.save_to_file(Path::new(filename))?;
@@ -1007,20 +1989,40 @@ Relevant developments:
         
         let state = ColonyState::load_from_file(Path::new(filename))?;
         self.mission = state.mission;
+        self.cycle = state.total_cycles as u64;
+        self.lineage = state.lineage;
+
+        if let Some(lenia_state) = &state.lenia_world {
+            if let Err(e) = self.lenia_world.restore_state(lenia_state) {
+                eprintln!("Error restoring Lenia field: {}", e);
+            }
+        }
         
         // Clear existing cells and load from state
         self.cells.clear();
+        self.cell_positions.clear();
+        self.spatial.clear();
         for (id, cell_state) in state.cells {
-            if let Some(cell) = self.cells.get_mut(&id) {
-                cell.energy = cell_state.energy;
-                cell.thoughts = VecDeque::from(cell_state.thoughts);
-                cell.current_plan = cell_state.current_plan;
-                cell.dimensional_position = cell_state.dimensional_position;
-                cell.dopamine = cell_state.dopamine;
-                cell.stability = cell_state.stability;
-                cell.phase = cell_state.phase;
-                cell.context_alignment_score = cell_state.context_alignment_score;
-                cell.mission_alignment_score = cell_state.mission_alignment_score;
+            let mut cell = Cell::new(Coordinates { x: cell_state.x, y: cell_state.y, z: cell_state.z, ..Default::default() });
+            cell.id = id;
+            cell.energy = cell_state.energy;
+            cell.thoughts = VecDeque::from(cell_state.thoughts);
+            cell.current_plan = cell_state.current_plan;
+            cell.dimensional_position = cell_state.dimensional_position;
+            cell.dopamine = cell_state.dopamine;
+            cell.stability = cell_state.stability;
+            cell.phase = cell_state.phase;
+            cell.context_alignment_score = cell_state.context_alignment_score;
+            cell.mission_alignment_score = cell_state.mission_alignment_score;
+            cell.lenia_state = cell_state.lenia_state;
+            cell.lenia_influence = cell_state.lenia_influence;
+            cell.parent_id = cell_state.parent_id;
+            cell.co_parent_id = cell_state.co_parent_id;
+            // States saved before the lineage was stored start new family trees
+            if self.lineage.get(&id).is_some() {
+                self.place_cell(cell);
+            } else {
+                self.insert_cell(cell);
             }
         }
         
@@ -1138,6 +2140,17 @@ Relevant developments:
         println!("║ │ Average Cell Energy      │ {:<21.2} │ ║", stats.average_cell_energy);
         println!("║ │ Highest Evolution Stage  │ {:<21} │ ║", stats.highest_evolution_stage);
         println!("║ │ Total Cycles             │ {:<21} │ ║", stats.total_cycles);
+        println!("║ │ LLM Cache Hits           │ {:<21} │ ║", stats.cache_hits);
+        println!("║ │ LLM Cache Misses         │ {:<21} │ ║", stats.cache_misses);
+        println!("║ │ Malformed LLM Responses  │ {:<21} │ ║", stats.malformed_responses);
+        println!("║ │ Repaired LLM Responses   │ {:<21} │ ║", stats.repaired_responses);
+        println!("║ │ Duplicate Thoughts       │ {:<21} │ ║", stats.duplicate_thoughts);
+        println!("║ │ Average Thought Novelty  │ {:<21.2} │ ║", stats.average_novelty);
+        println!("║ │ Phase Synchronization    │ {:<21.2} │ ║", stats.phase_order);
+        println!("║ │ Carrying Capacity        │ {:<21} │ ║", stats.carrying_capacity);
+        println!("║ │ Dormant Cells            │ {:<21} │ ║", stats.dormant_cells);
+        println!("║ │ Deaths                   │ {:<21} │ ║", stats.deaths);
+        println!("║ │ Energy Spent             │ {:<21.1} │ ║", stats.energy_spent);
         println!("║ └──────────────────────────┴───────────────────────────┘ ║");
         println!("╚════════════════════════════════════════════════════════════╝");
 
@@ -1223,7 +2236,7 @@ Relevant developments:
             if let Some(plan) = &cell.current_plan {
                 // Calculate plan execution metrics
                 let completed_nodes = plan.nodes.iter()
-                    .filter(|n| n.estimated_completion > 0.8)
+                    .filter(|n| n.progress > 0.8)
                     .count() as f64;
                 let total_nodes = plan.nodes.len() as f64;
                 let execution_rate = if total_nodes > 0.0 {
@@ -1274,13 +2287,33 @@ Relevant developments:
             total_cells: self.cells.len() as u32,
             total_thoughts: 0,
             total_plans: 0,
-            successful_plans: 0,
-            failed_plans: 0,
+            successful_plans: self.plans_completed as u32,
+            failed_plans: self.plans_failed as u32,
             average_cell_energy: 0.0,
             highest_evolution_stage: 0,
             total_cycles: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            malformed_responses: 0,
+            repaired_responses: 0,
+            duplicate_thoughts: self.cells.values().map(|c| c.duplicate_thoughts as u64).sum(),
+            average_novelty: self.get_average_novelty(),
+            phase_order: self.get_phase_order(),
+            dormant_cells: self.get_dormant_count() as u32,
+            deaths: self.deaths,
+            energy_spent: self.get_energy_spent(),
+            carrying_capacity: self.carrying_capacity as u32,
         };
 
+        if let Some(cache_stats) = self.api_client.cache_stats() {
+            stats.cache_hits = cache_stats.hits;
+            stats.cache_misses = cache_stats.misses;
+        }
+
+        let repairs = repair_stats();
+        stats.malformed_responses = repairs.malformed;
+        stats.repaired_responses = repairs.repaired;
+
         for cell in self.cells.values() {
             stats.total_thoughts += cell.thoughts.len() as u32;
             stats.average_cell_energy += cell.energy;
@@ -1309,36 +2342,27 @@ Relevant developments:
     }
 
     pub async fn process_cell_thoughts(&mut self, cell_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+        let colony_recent = self.recent_colony_thoughts();
+        let messages = self.messages.take(cell_id, self.cycle);
+        let mut published = None;
         if let Some(cell) = self.cells.get_mut(&cell_id) {
-            cell.generate_thought(&*self.api_client, &self.mission).await?;
+            let inbox: Vec<String> = messages.iter().map(Message::summary).collect();
+            let previous = cell.thoughts.back().map(|t| t.id.clone());
+            if let Err(e) = cell.generate_thought(&*self.api_client, self.embedding_client.as_deref(), &colony_recent, &self.mission, &inbox).await {
+                self.messages.requeue(cell_id, messages);
+                return Err(e);
+            }
+            cell.receive(&messages);
+            published = cell.thoughts.back().map(|t| t.id.clone()).filter(|id| previous.as_ref() != Some(id));
+        }
+        if let Some(thought_id) = published {
+            self.publish_thought(cell_id, &thought_id);
         }
         Ok(())
     }
 
     pub fn get_cluster_count(&self) -> usize {
-        let mut visited = std::collections::HashSet::new();
-        let mut clusters = 0;
-        
-        for &id in self.cells.keys() {
-            if !visited.contains(&id) {
-                self.explore_cluster(id, &mut visited);
-                clusters += 1;
-            }
-        }
-        
-        clusters
-    }
-
-    fn explore_cluster(&self, cell_id: Uuid, visited: &mut std::collections::HashSet<Uuid>) {
-        if !visited.insert(cell_id) {
-            return;
-        }
-        
-        if let Some(cell) = self.cells.get(&cell_id) {
-            for &neighbor_id in &cell.neighbors {
-                self.explore_cluster(neighbor_id, visited);
-            }
-        }
+        self.spatial.clusters(NEIGHBOR_DISTANCE_THRESHOLD).len()
     }
 
     pub fn get_max_depth(&self) -> usize {
@@ -1358,6 +2382,22 @@ Relevant developments:
         total_energy / self.cells.len() as f64
     }
 
+    pub fn get_average_novelty(&self) -> f64 {
+        let (sum, count) = self.cells.values()
+            .flat_map(|cell| cell.thoughts.iter())
+            .fold((0.0, 0), |(sum, count), t| (sum + t.novelty_score, count + 1));
+        if count > 0 {
+            sum / count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// How synchronized the cells' LTL phases are, from 0.0 to 1.0.
+    pub fn get_phase_order(&self) -> f64 {
+        phase_order(self.cells.values().map(|cell| cell.enhanced_state.phase))
+    }
+
     pub fn get_total_thoughts(&self) -> usize {
         self.cells.values()
             .map(|cell| cell.thoughts.len())
@@ -1381,6 +2421,32 @@ Relevant developments:
         evolved_cells / total_cells
     }
 }
+// A point next to the parent with its heatmap scores lightly varied
+fn offspring_position(parent: &Coordinates, rng: &mut impl Rng) -> Coordinates {
+    let mut new_position = Coordinates {
+        x: parent.x + (rng.gen::<f64>() - 0.5),
+        y: parent.y + (rng.gen::<f64>() - 0.5),
+        z: parent.z + (rng.gen::<f64>() - 0.5),
+        heat: parent.heat * (0.9 + rng.gen::<f64>() * 0.2), // Inherit with slight variation
+        emergence_score: parent.emergence_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        coherence_score: parent.coherence_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        resilience_score: parent.resilience_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        intelligence_score: parent.intelligence_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        efficiency_score: parent.efficiency_score + (rng.gen::<f64>() * 10.0 - 5.0),
+        integration_score: parent.integration_score + (rng.gen::<f64>() * 10.0 - 5.0),
+    };
+    
+    // Clamp all scores to valid ranges
+    new_position.heat = new_position.heat.clamp(0.0, 1.0);
+    new_position.emergence_score = new_position.emergence_score.clamp(-100.0, 100.0);
+    new_position.coherence_score = new_position.coherence_score.clamp(-100.0, 100.0);
+    new_position.resilience_score = new_position.resilience_score.clamp(-100.0, 100.0);
+    new_position.intelligence_score = new_position.intelligence_score.clamp(-100.0, 100.0);
+    new_position.efficiency_score = new_position.efficiency_score.clamp(-100.0, 100.0);
+    new_position.integration_score = new_position.integration_score.clamp(-100.0, 100.0);
+    new_position
+}
+
 fn calculate_dimensional_complement(pos1: &DimensionalPosition, pos2: &DimensionalPosition) -> f64 {
     // Calculate how well two positions complement each other
     // Higher score means their differences tend to balance toward 0
@@ -1400,8 +2466,256 @@ fn calculate_dimensional_complement(pos1: &DimensionalPosition, pos2: &Dimension
     let max_possible_diff = 1200.0; // 6 dimensions * 200 (max range of -100 to 100)
     1.0 - (total_complement / max_possible_diff)
 }
-fn calculate_distance(pos1: &Coordinates, pos2: &Coordinates) -> f64 {
-    ((pos2.x - pos1.x).powi(2) + 
-     (pos2.y - pos1.y).powi(2) + 
-     (pos2.z - pos1.z).powi(2)).sqrt()
+
+/// Picks up to `MAX_THOUGHTS_FOR_PLAN` thoughts that are relevant but not
+/// redundant with each other. When the mission is embedded, each candidate's
+/// score is scaled by how closely the thought matches the mission.
+fn select_plan_thoughts(candidates: Vec<(Thought, f64)>, mission_embedding: Option<&[f32]>) -> Vec<Thought> {
+    let max_score = candidates.iter().map(|(_, score)| *score).fold(0.0, f64::max);
+    let scored: Vec<(Option<&[f32]>, f64)> = candidates.iter()
+        .map(|(thought, score)| {
+            let embedding = thought.embedding.as_deref();
+            let normalized = if max_score > 0.0 { score / max_score } else { 0.0 };
+            let mission_alignment = match (embedding, mission_embedding) {
+                (Some(e), Some(m)) => (1.0 + cosine_similarity(e, m) as f64) / 2.0,
+                _ => 1.0,
+            };
+            (embedding, normalized * mission_alignment)
+        })
+        .collect();
+
+    let selected = select_diverse(&scored, MAX_THOUGHTS_FOR_PLAN, PLAN_THOUGHT_RELEVANCE_WEIGHT);
+    selected.into_iter().map(|i| candidates[i].0.clone()).collect()
+}
+
+/// Channel 0 carries cell energy and activity. Channels 1-6 carry the six
+/// dimensions in `DimensionalPosition::axes` order; each sustains itself and
+/// also feeds the next dimension, so neighbouring axes influence each other.
+// Channel 0 is the activity field, 1 to 6 hold each Thought DNA axis weighted
+// by mass and the last holds the mass itself
+const DIMENSION_MASS_CHANNEL: usize = DIMENSION_AXES.len() + 1;
+
+fn colony_lenia_params() -> LeniaParams {
+    let base = LeniaParams {
+        grid_size: LENIA_GRID_SIZE,
+        kernel_radius: LENIA_KERNEL_RADIUS,
+        channels: DIMENSION_MASS_CHANNEL + 1,
+        ..LeniaParams::default()
+    };
+
+    // Only the activity channel grows; the dimension channels are passive
+    LeniaParams { kernels: base.channel_kernels(), ..base }
+}
+
+// How far a cell's Thought DNA reaches its neighbours through the field
+fn dimension_kernel() -> ChannelKernel {
+    ChannelKernel {
+        source: 1,
+        target: 1,
+        kernel_radius: LENIA_KERNEL_RADIUS,
+        shape: KernelShape::Gaussian { sigma: 3.0 },
+        growth: GrowthFunction::Gaussian { mu: 0.5, sigma: 0.3 },
+        weight: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::local_llm::LocalLLMClient;
+    use crate::models::types::RealTimeContext;
+    use async_trait::async_trait;
+
+    fn at(x: f64, y: f64, z: f64) -> Coordinates {
+        Coordinates { x, y, z, ..Default::default() }
+    }
+
+    #[test]
+    fn test_neighbor_graph_stays_symmetric() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let ids: Vec<Uuid> = (0..30)
+            .map(|i| colony.add_cell(at((i % 6) as f64 * 1.2, (i / 6) as f64 * 1.2, 0.0)))
+            .collect();
+        assert_eq!(colony.check_neighbor_graph(), Ok(()));
+
+        colony.move_cell(ids[0], at(20.0, 20.0, 0.0));
+        colony.move_cell(ids[7], at(19.0, 20.0, 0.0));
+        colony.remove_cell(ids[14]);
+        assert_eq!(colony.check_neighbor_graph(), Ok(()));
+        assert_eq!(colony.cells[&ids[0]].neighbors, vec![ids[7]]);
+    }
+
+    #[test]
+    fn test_saved_state_restores_cells_and_lineage() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let parent = colony.add_cell(at(0.0, 0.0, 0.0));
+        let co_parent = colony.add_cell(at(1.0, 0.0, 0.0));
+        let mut child = colony.cells[&parent].offspring(at(0.5, 0.5, 0.0), &mut rand::thread_rng());
+        child.co_parent_id = Some(co_parent);
+        let child = colony.insert_cell(child);
+
+        let path = std::env::temp_dir().join(format!("colony_state_{}.json", Uuid::new_v4()));
+        colony.save_state_to_file(path.to_str().unwrap()).unwrap();
+        let mut restored = Colony::new("other", Box::new(LocalLLMClient::new().unwrap()));
+        restored.load_state_from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.cells.len(), 3);
+        assert_eq!(restored.cells[&child].co_parent_id, Some(co_parent));
+        assert_eq!(restored.lineage.ancestors(child), vec![parent]);
+        assert_eq!(restored.lineage.get(&child).unwrap().co_parent_id, Some(co_parent));
+        assert_eq!(restored.check_neighbor_graph(), Ok(()));
+    }
+
+    #[test]
+    fn test_llm_calls_are_split_between_cells() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let first = colony.add_cell(at(0.0, 0.0, 0.0));
+        let second = colony.add_cell(at(1.0, 0.0, 0.0));
+        let before = colony.cells[&first].energy;
+
+        colony.charge_llm_calls(&[first, second], 3);
+        assert!((before - colony.cells[&first].energy - 1.5 * ENERGY_COST_PER_LLM_CALL).abs() < 1e-9);
+        assert_eq!(colony.cells[&first].energy, colony.cells[&second].energy);
+    }
+
+    #[test]
+    fn test_energy_transfers_conserve_energy() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let donor = colony.add_cell(at(0.0, 0.0, 0.0));
+        let recipient = colony.add_cell(at(1.0, 0.0, 0.0));
+        colony.cells.get_mut(&donor).unwrap().energy = 30.0;
+        colony.cells.get_mut(&recipient).unwrap().energy = 95.0;
+
+        // Only the recipient's headroom moves
+        assert_eq!(colony.transfer_energy(donor, recipient, 20.0), 5.0);
+        assert_eq!(colony.cells[&donor].energy + colony.cells[&recipient].energy, 125.0);
+
+        // And never more than the donor has
+        colony.cells.get_mut(&recipient).unwrap().energy = 10.0;
+        assert_eq!(colony.transfer_energy(donor, recipient, 50.0), 25.0);
+        assert_eq!(colony.cells[&donor].energy, 0.0);
+        assert_eq!(colony.cells[&recipient].energy, 35.0);
+        assert_eq!(colony.transfer_energy(donor, Uuid::new_v4(), 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_saved_energy_grid_covers_the_bounding_box() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let west = colony.add_cell(at(-500.0, 3.0, 0.0));
+        colony.add_cell(at(-498.0, 4.0, 1.0));
+
+        let path = std::env::temp_dir().join(format!("colony_state_{}.json", Uuid::new_v4()));
+        colony.save_state_to_file(path.to_str().unwrap()).unwrap();
+        let state: crate::models::state::ColonyState = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(state.energy_grid.size, 5);
+        assert_eq!(state.energy_grid.origin, [-500.0, 3.0, 0.0]);
+        assert_eq!(state.energy_grid.cell_positions[&west], (0, 0, 0));
+    }
+
+    #[test]
+    fn test_lenia_coupling_pulls_toward_neighbours_mean() {
+        let mut colony = Colony::new("test", Box::new(LocalLLMClient::new().unwrap()));
+        let high = colony.add_cell(at(0.0, 0.0, 0.0));
+        let low = colony.add_cell(at(2.0, 0.0, 0.0));
+        let alone = colony.add_cell(at(15.0, 15.0, 15.0));
+        for (id, value) in [(high, 60.0), (low, -60.0), (alone, 40.0)] {
+            for axis in colony.cells.get_mut(&id).unwrap().dimensional_position.axes_mut() {
+                *axis = value;
+            }
+        }
+
+        for _ in 0..8 {
+            colony.step_lenia_field();
+        }
+        let emergence = |id: Uuid| colony.cells[&id].dimensional_position.emergence;
+        assert!(emergence(high) < 55.0 && emergence(high) > 0.0, "{}", emergence(high));
+        assert!(emergence(low) > -55.0 && emergence(low) < 0.0, "{}", emergence(low));
+        // The pair closes its gap around a midpoint near zero
+        assert!(emergence(high) - emergence(low) < 100.0);
+        assert!((emergence(high) + emergence(low)).abs() < 20.0, "{} {}", emergence(high), emergence(low));
+        // An isolated cell only sees itself, so it neither sinks nor saturates
+        assert!((emergence(alone) - 40.0).abs() < 1.0, "{}", emergence(alone));
+    }
+
+    // Answers every prompt with the same text
+    struct ScriptedClient(&'static str);
+
+    #[async_trait]
+    impl ModelClient for ScriptedClient {
+        async fn generate_contextual_thought(&self, _: &CellContext, _: &RealTimeContext, _: &str) -> Result<(String, f64, Vec<String>), Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn create_plan(&self, _: &[Thought]) -> Result<Plan, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn evaluate_dimensional_state(&self, _: &DimensionalPosition, _: &[Thought], _: &[Plan]) -> Result<(f64, f64), Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn compress_memories(&self, _: &[String]) -> Result<String, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn gather_real_time_context(&self, _: Option<Vec<String>>) -> Result<RealTimeContext, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn generate_contextual_thoughts_batch(
+            &self,
+            _: &[(Uuid, &CellContext)],
+            _: &RealTimeContext,
+            _: &str,
+            _: &[Thought],
+        ) -> Result<HashMap<Uuid, Vec<(String, f64, Vec<String>)>>, Box<dyn Error>> {
+            unimplemented!()
+        }
+
+        async fn query_llm(&self, _: &str) -> Result<String, Box<dyn Error>> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lone_plans_are_synthesised_and_scored_like_merged_ones() {
+        let script = "SUMMARY: Survey then build\nCOMPONENT: Survey\nDEPENDS_ON: none\nCOMPONENT: Build\nDEPENDS_ON: Survey\n";
+        let mut colony = Colony::new("test", Box::new(ScriptedClient(script)));
+        let cell = colony.add_cell(at(0.0, 0.0, 0.0));
+        let thought = |relevance_score: f64| Thought {
+            id: Uuid::new_v4().to_string(),
+            content: "Map the terrain".to_string(),
+            timestamp: Utc::now(),
+            relevance_score,
+            context_tags: Vec::new(),
+            real_time_factors: Vec::new(),
+            confidence_score: 0.5,
+            ascii_visualization: None,
+            referenced_thoughts: Vec::new(),
+            embedding: None,
+            novelty_score: 1.0,
+        };
+        let lone = Plan {
+            id: Uuid::new_v4(),
+            score: 0.1,
+            thoughts: vec![thought(0.9), thought(0.5)],
+            nodes: parse_plan_nodes("COMPONENT: Wander").unwrap(),
+            summary: "Wander".to_string(),
+            participating_cells: vec![cell],
+            created_at: Utc::now(),
+            status: PlanStatus::Proposed,
+        };
+        let before = colony.cells[&cell].energy;
+
+        let master = colony.synthesize_candidate(&[&lone]).await;
+        assert_ne!(master.id, lone.id);
+        assert_eq!(master.summary, "Survey then build");
+        assert_eq!(master.nodes.len(), 2);
+        assert!((master.score - 0.7).abs() < 1e-9);
+        assert_eq!(master.participating_cells, vec![cell]);
+        assert!((before - colony.cells[&cell].energy - ENERGY_COST_PER_LLM_CALL).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,143 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+//! Colony-wide agreement on a plan. Similar proposals are clustered so each
+//! cluster can be merged into one candidate, then every cell votes for the
+//! candidate whose participants' Thought DNA lies closest to its own.
+
+use crate::api::embeddings::cosine_similarity;
+use crate::models::types::Plan;
+use crate::systems::novelty::{jaccard, shingles};
+use crate::systems::spatial::UnionFind;
+
+// Farthest two points can be apart on six axes spanning -100..100
+const MAX_DNA_DISTANCE: f64 = 489.8979485566356;
+
+/// Text a plan is compared by: its summary and step titles.
+pub fn plan_text(plan: &Plan) -> String {
+    std::iter::once(plan.summary.as_str())
+        .chain(plan.nodes.iter().map(|node| node.title.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Groups plans whose similarity reaches `threshold`, transitively. Uses
+/// embeddings when one is given per plan, otherwise word shingles.
+pub fn cluster_plans(texts: &[String], embeddings: Option<&[Vec<f32>]>, threshold: f64) -> Vec<Vec<usize>> {
+    let embeddings = embeddings.filter(|e| e.len() == texts.len());
+    let shingled: Vec<_> = texts.iter().map(|text| shingles(text)).collect();
+    let mut sets = UnionFind::new(texts.len());
+
+    for a in 0..texts.len() {
+        for b in a + 1..texts.len() {
+            let similarity = match embeddings {
+                Some(e) => cosine_similarity(&e[a], &e[b]) as f64,
+                None => jaccard(&shingled[a], &shingled[b]),
+            };
+            if similarity >= threshold {
+                sets.union(a, b);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut roots: Vec<usize> = Vec::new();
+    for index in 0..texts.len() {
+        let root = sets.find(index);
+        match roots.iter().position(|&r| r == root) {
+            Some(cluster) => clusters[cluster].push(index),
+            None => {
+                roots.push(root);
+                clusters.push(vec![index]);
+            }
+        }
+    }
+    clusters
+}
+
+/// How close two Thought DNA positions are, from 0 (opposite corners) to 1.
+pub fn alignment(a: &[f64; 6], b: &[f64; 6]) -> f64 {
+    let distance = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt();
+    (1.0 - distance / MAX_DNA_DISTANCE).clamp(0.0, 1.0)
+}
+
+/// Mean of the given positions, or the origin when there are none.
+pub fn centroid(positions: &[[f64; 6]]) -> [f64; 6] {
+    let mut mean = [0.0; 6];
+    for position in positions {
+        for (m, value) in mean.iter_mut().zip(position) {
+            *m += value / positions.len() as f64;
+        }
+    }
+    mean
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ballot {
+    pub choice: usize,
+    pub weight: f64,
+}
+
+/// A vote for the best aligned candidate, weighted by that alignment.
+pub fn vote(voter: &[f64; 6], candidates: &[[f64; 6]]) -> Option<Ballot> {
+    candidates.iter()
+        .enumerate()
+        .map(|(choice, candidate)| Ballot { choice, weight: alignment(voter, candidate) })
+        .max_by(|a, b| a.weight.total_cmp(&b.weight))
+}
+
+/// The candidate with the most weight and its share of all votes cast.
+pub fn tally(ballots: &[Ballot], candidates: usize) -> Option<(usize, f64)> {
+    let mut totals = vec![0.0; candidates];
+    for ballot in ballots {
+        totals[ballot.choice] += ballot.weight;
+    }
+    let total: f64 = totals.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    totals.iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(winner, votes)| (winner, votes / total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clusters_similar_plans() {
+        let texts = vec![
+            "Build a shared knowledge graph of research findings".to_string(),
+            "Build a shared knowledge graph of research findings and experiments".to_string(),
+            "Reduce energy use of the compute cluster overnight".to_string(),
+        ];
+        assert_eq!(cluster_plans(&texts, None, 0.5), vec![vec![0, 1], vec![2]]);
+
+        let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 1.0]];
+        assert_eq!(cluster_plans(&texts, Some(&embeddings), 0.9), vec![vec![0], vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_voters_pick_the_closest_candidate() {
+        let candidates = [[50.0; 6], [-50.0; 6]];
+        let ballots: Vec<Ballot> = [[40.0; 6], [60.0; 6], [-45.0; 6]].iter()
+            .filter_map(|voter| vote(voter, &candidates))
+            .collect();
+        assert_eq!(ballots.iter().map(|b| b.choice).collect::<Vec<_>>(), vec![0, 0, 1]);
+
+        let (winner, share) = tally(&ballots, candidates.len()).unwrap();
+        assert_eq!(winner, 0);
+        assert!(share > 0.6 && share < 0.7, "{}", share);
+        assert_eq!(alignment(&[100.0; 6], &[-100.0; 6]), 0.0);
+    }
+}
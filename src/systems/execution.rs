@@ -0,0 +1,208 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::types::{Plan, PlanNode, PlanNodeStatus, PlanStatus};
+use std::collections::HashSet;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// How a plan step that has reached full progress is judged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlanEvaluator {
+    // Pass with a probability from the plan's relevance and the participants' energy
+    Rules,
+    // Ask the model, falling back to the rules if it gives no verdict
+    Llm,
+}
+
+impl FromStr for PlanEvaluator {
+    type Err = String;
+
+    fn from_str(evaluator: &str) -> Result<Self, Self::Err> {
+        match evaluator {
+            "rules" => Ok(PlanEvaluator::Rules),
+            "llm" => Ok(PlanEvaluator::Llm),
+            _ => Err(format!("Unknown plan evaluator '{}'", evaluator)),
+        }
+    }
+}
+
+fn is_done(node: &PlanNode) -> bool {
+    matches!(node.status, PlanNodeStatus::Completed)
+}
+
+fn is_dead(node: &PlanNode) -> bool {
+    matches!(node.status, PlanNodeStatus::Failed | PlanNodeStatus::Blocked)
+}
+
+/// Indices of the unfinished steps whose dependencies have all completed.
+/// Dependencies on ids outside the plan are ignored.
+pub fn ready_nodes(plan: &Plan) -> Vec<usize> {
+    let ids: HashSet<Uuid> = plan.nodes.iter().map(|node| node.id).collect();
+    let done: HashSet<Uuid> = plan.nodes.iter().filter(|node| is_done(node)).map(|node| node.id).collect();
+    plan.nodes.iter()
+        .enumerate()
+        .filter(|(_, node)| matches!(node.status, PlanNodeStatus::Pending | PlanNodeStatus::InProgress))
+        .filter(|(_, node)| node.dependencies.iter().all(|dep| done.contains(dep) || !ids.contains(dep)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Moves every ready step's `progress` forward by `work` and returns the
+/// steps that reached 1.0 and need judging.
+pub fn advance(plan: &mut Plan, work: f64) -> Vec<usize> {
+    if matches!(plan.status, PlanStatus::Proposed) {
+        plan.status = PlanStatus::InProgress;
+    }
+    let mut finished = Vec::new();
+    for index in ready_nodes(plan) {
+        let node = &mut plan.nodes[index];
+        node.status = PlanNodeStatus::InProgress;
+        node.progress = (node.progress + work).min(1.0);
+        if node.progress >= 1.0 {
+            finished.push(index);
+        }
+    }
+    finished
+}
+
+/// Blocks steps that can never run, then completes the plan once every step
+/// is done or fails it once any step has failed or been blocked. A plan with
+/// no steps fails, since there is nothing it could complete.
+pub fn settle(plan: &mut Plan) {
+    loop {
+        let dead: HashSet<Uuid> = plan.nodes.iter().filter(|node| is_dead(node)).map(|node| node.id).collect();
+        let mut changed = false;
+        for node in plan.nodes.iter_mut().filter(|node| matches!(node.status, PlanNodeStatus::Pending)) {
+            if node.dependencies.iter().any(|dep| dead.contains(dep)) {
+                node.status = PlanNodeStatus::Blocked;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Steps left waiting with nothing runnable sit on a dependency cycle
+    let stuck = ready_nodes(plan).is_empty() && plan.nodes.iter().any(|node| matches!(node.status, PlanNodeStatus::Pending));
+    if stuck {
+        for node in plan.nodes.iter_mut().filter(|node| matches!(node.status, PlanNodeStatus::Pending)) {
+            node.status = PlanNodeStatus::Blocked;
+        }
+    }
+
+    if plan.nodes.is_empty() || plan.nodes.iter().any(is_dead) {
+        plan.status = PlanStatus::Failed;
+    } else if plan.nodes.iter().all(is_done) {
+        plan.status = PlanStatus::Completed;
+    }
+}
+
+/// Chance that a finished step holds up under the rule evaluator.
+pub fn rule_pass_chance(plan: &Plan, node: &PlanNode, mean_energy: f64) -> f64 {
+    let described = if node.description.trim().is_empty() { 0.0 } else { 0.2 };
+    0.4 * plan.score.clamp(0.0, 1.0) + 0.4 * (mean_energy / 100.0).clamp(0.0, 1.0) + described
+}
+
+/// Reads a `VERDICT: COMPLETED|FAILED` line from a step review.
+pub fn parse_verdict(response: &str) -> Option<bool> {
+    let verdict = response.lines()
+        .find_map(|line| line.trim().trim_start_matches('*').strip_prefix("VERDICT:"))?
+        .trim()
+        .to_uppercase();
+    if verdict.starts_with("COMPLETED") {
+        Some(true)
+    } else if verdict.starts_with("FAILED") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn node(title: &str, dependencies: Vec<Uuid>) -> PlanNode {
+        PlanNode {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: String::new(),
+            dependencies,
+            estimated_completion: 0.0,
+            progress: 0.0,
+            status: PlanNodeStatus::Pending,
+        }
+    }
+
+    fn plan(nodes: Vec<PlanNode>) -> Plan {
+        Plan {
+            id: Uuid::new_v4(),
+            thoughts: Vec::new(),
+            nodes,
+            summary: String::new(),
+            score: 0.5,
+            participating_cells: Vec::new(),
+            created_at: Utc::now(),
+            status: PlanStatus::Proposed,
+        }
+    }
+
+    #[test]
+    fn test_steps_run_in_dependency_order() {
+        let first = node("first", Vec::new());
+        let second = node("second", vec![first.id]);
+        let mut plan = plan(vec![second, first]);
+
+        assert_eq!(advance(&mut plan, 1.0), vec![1]);
+        assert_eq!(plan.nodes[0].progress, 0.0);
+        plan.nodes[1].status = PlanNodeStatus::Completed;
+        assert_eq!(advance(&mut plan, 1.0), vec![0]);
+        plan.nodes[0].status = PlanNodeStatus::Completed;
+        settle(&mut plan);
+        assert!(matches!(plan.status, PlanStatus::Completed));
+    }
+
+    #[test]
+    fn test_failure_blocks_dependents_and_cycles_fail() {
+        let first = node("first", Vec::new());
+        let second = node("second", vec![first.id]);
+        let mut failing = plan(vec![first, second]);
+        failing.nodes[0].status = PlanNodeStatus::Failed;
+        settle(&mut failing);
+        assert!(matches!(failing.nodes[1].status, PlanNodeStatus::Blocked));
+        assert!(matches!(failing.status, PlanStatus::Failed));
+
+        let mut a = node("a", Vec::new());
+        let b = node("b", vec![a.id]);
+        a.dependencies.push(b.id);
+        let mut cyclic = plan(vec![a, b]);
+        settle(&mut cyclic);
+        assert!(matches!(cyclic.status, PlanStatus::Failed));
+    }
+
+    #[test]
+    fn test_empty_plans_fail() {
+        let mut empty = plan(Vec::new());
+        assert!(advance(&mut empty, 1.0).is_empty());
+        settle(&mut empty);
+        assert!(matches!(empty.status, PlanStatus::Failed));
+    }
+
+    #[test]
+    fn test_reads_verdicts() {
+        assert_eq!(parse_verdict("VERDICT: COMPLETED\nREASON: done"), Some(true));
+        assert_eq!(parse_verdict("**VERDICT: failed - vague"), Some(false));
+        assert_eq!(parse_verdict("Looks fine"), None);
+    }
+}
@@ -8,10 +8,21 @@ The above copyright notice and this permission notice shall be included in all c
 
 THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
 
+use crate::models::state::LeniaWorldState;
 use crate::models::types::Coordinates;
-use ndarray::Array3;
+use ndarray::{s, Array3, Axis, Zip};
+use num_complex::Complex64;
+use rustfft::{Fft, FftPlanner};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LeniaParams {
     pub kernel_radius: f64,
     pub kernel_sigma: f64,
@@ -20,6 +31,13 @@ pub struct LeniaParams {
     pub time_step: f64,
     pub grid_size: usize,
     pub dt: f64,
+    pub channels: usize,
+    // Empty means a single channel-0 kernel built from the fields above
+    pub kernels: Vec<ChannelKernel>,
+    // Cells along x, y and z; None is a grid_size cube. A z extent of 1 is a 2D world
+    pub extent: Option<[usize; 3]>,
+    pub boundary: Boundary,
+    pub transform: GridTransform,
 }
 
 impl Default for LeniaParams {
@@ -32,118 +50,886 @@ impl Default for LeniaParams {
             time_step: 0.1,
             grid_size: 256,
             dt: 0.1,
+            channels: 1,
+            kernels: Vec::new(),
+            extent: None,
+            boundary: Boundary::Periodic,
+            transform: GridTransform::default(),
         }
     }
 }
 
+impl LeniaParams {
+    /// A flat `width` x `height` world, one cell deep.
+    pub fn planar(width: usize, height: usize) -> Self {
+        Self {
+            grid_size: width.max(height),
+            extent: Some([width, height, 1]),
+            ..Self::default()
+        }
+    }
+
+    pub fn shape(&self) -> [usize; 3] {
+        self.extent.unwrap_or([self.grid_size; 3])
+    }
+
+    pub fn channel_kernels(&self) -> Vec<ChannelKernel> {
+        if !self.kernels.is_empty() {
+            return self.kernels.clone();
+        }
+
+        vec![ChannelKernel {
+            source: 0,
+            target: 0,
+            kernel_radius: self.kernel_radius,
+            shape: KernelShape::Gaussian { sigma: self.kernel_sigma },
+            growth: GrowthFunction::Gaussian { mu: self.growth_mu, sigma: self.growth_sigma },
+            weight: 1.0,
+        }]
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// A named parameter set from `PRESETS` together with a seed that starts it.
+    pub fn preset(name: &str) -> Option<(Self, SeedPattern)> {
+        let single = |radius: f64, shape: KernelShape, growth: GrowthFunction, seed: SeedPattern| {
+            let params = Self {
+                kernel_radius: radius,
+                kernels: vec![ChannelKernel { source: 0, target: 0, kernel_radius: radius, shape, growth, weight: 1.0 }],
+                ..Self::planar(64, 64)
+            };
+            Some((params, seed))
+        };
+
+        match name {
+            "orbium" => single(
+                13.0,
+                KernelShape::Exponential { alpha: 4.0 },
+                GrowthFunction::Gaussian { mu: 0.15, sigma: 0.015 },
+                SeedPattern::Cells { cells: ORBIUM.iter().map(|row| row.to_vec()).collect() },
+            ),
+            "ring-soup" => single(
+                18.0,
+                KernelShape::MultiRing { peaks: vec![0.5, 1.0, 2.0 / 3.0] },
+                GrowthFunction::Gaussian { mu: 0.26, sigma: 0.036 },
+                SeedPattern::Noise { radius: 14, density: 0.6, seed: 7 },
+            ),
+            "smooth-blob" => single(
+                8.0,
+                KernelShape::Polynomial { alpha: 4 },
+                GrowthFunction::Polynomial { mu: 0.3, sigma: 0.05 },
+                SeedPattern::Blob { radius: 6, amplitude: 0.8 },
+            ),
+            "pulsing-shell" => single(
+                10.0,
+                KernelShape::MultiRing { peaks: vec![1.0, 0.3] },
+                GrowthFunction::Step { mu: 0.2, sigma: 0.05 },
+                SeedPattern::Shell { radius: 8, width: 2, amplitude: 1.0 },
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Names accepted by `LeniaParams::preset`, all 64x64 planar worlds. `orbium`
+/// is Orbium unicaudatus from Chan's Lenia papers, parameters and seed, and
+/// glides across the world. `ring-soup` uses the kernel and growth of
+/// Hydrogeminium natans on random noise, so what emerges varies with the seed.
+/// The other two are smooth, forgiving settings for quick experiments.
+pub const PRESETS: &[&str] = &["orbium", "ring-soup", "smooth-blob", "pulsing-shell"];
+
+// Orbium unicaudatus as published with Lenia, 20x20 cells
+const ORBIUM: [[f64; 20]; 20] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.1, 0.14, 0.1, 0.0, 0.0, 0.03, 0.03, 0.0, 0.0, 0.3, 0.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.08, 0.24, 0.3, 0.3, 0.18, 0.14, 0.15, 0.16, 0.15, 0.09, 0.2, 0.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.15, 0.34, 0.44, 0.46, 0.38, 0.18, 0.14, 0.11, 0.13, 0.19, 0.18, 0.45, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.06, 0.13, 0.39, 0.5, 0.5, 0.37, 0.06, 0.0, 0.0, 0.0, 0.02, 0.16, 0.68, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.11, 0.17, 0.17, 0.33, 0.4, 0.38, 0.28, 0.14, 0.0, 0.0, 0.0, 0.0, 0.0, 0.18, 0.42, 0.0, 0.0],
+    [0.0, 0.0, 0.09, 0.18, 0.13, 0.06, 0.08, 0.26, 0.32, 0.32, 0.27, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.82, 0.0, 0.0],
+    [0.27, 0.0, 0.16, 0.12, 0.0, 0.0, 0.0, 0.25, 0.38, 0.44, 0.45, 0.34, 0.0, 0.0, 0.0, 0.0, 0.0, 0.22, 0.17, 0.0],
+    [0.0, 0.07, 0.2, 0.02, 0.0, 0.0, 0.0, 0.31, 0.48, 0.57, 0.6, 0.57, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.49, 0.0],
+    [0.0, 0.59, 0.19, 0.0, 0.0, 0.0, 0.0, 0.2, 0.57, 0.69, 0.76, 0.76, 0.49, 0.0, 0.0, 0.0, 0.0, 0.0, 0.36, 0.0],
+    [0.0, 0.58, 0.19, 0.0, 0.0, 0.0, 0.0, 0.0, 0.67, 0.83, 0.9, 0.92, 0.87, 0.12, 0.0, 0.0, 0.0, 0.0, 0.22, 0.07],
+    [0.0, 0.0, 0.46, 0.0, 0.0, 0.0, 0.0, 0.0, 0.7, 0.93, 1.0, 1.0, 1.0, 0.61, 0.0, 0.0, 0.0, 0.0, 0.18, 0.11],
+    [0.0, 0.0, 0.82, 0.0, 0.0, 0.0, 0.0, 0.0, 0.47, 1.0, 1.0, 0.98, 1.0, 0.96, 0.27, 0.0, 0.0, 0.0, 0.19, 0.1],
+    [0.0, 0.0, 0.46, 0.0, 0.0, 0.0, 0.0, 0.0, 0.25, 1.0, 1.0, 0.84, 0.92, 0.97, 0.54, 0.14, 0.04, 0.1, 0.21, 0.05],
+    [0.0, 0.0, 0.0, 0.4, 0.0, 0.0, 0.0, 0.0, 0.09, 0.8, 1.0, 0.82, 0.8, 0.85, 0.63, 0.31, 0.18, 0.19, 0.2, 0.01],
+    [0.0, 0.0, 0.0, 0.36, 0.1, 0.0, 0.0, 0.0, 0.05, 0.54, 0.86, 0.79, 0.74, 0.72, 0.6, 0.39, 0.28, 0.24, 0.13, 0.0],
+    [0.0, 0.0, 0.0, 0.01, 0.3, 0.07, 0.0, 0.0, 0.08, 0.36, 0.64, 0.7, 0.64, 0.6, 0.51, 0.39, 0.29, 0.19, 0.04, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.1, 0.24, 0.14, 0.1, 0.15, 0.29, 0.45, 0.53, 0.52, 0.46, 0.4, 0.31, 0.21, 0.08, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.08, 0.21, 0.21, 0.22, 0.29, 0.36, 0.39, 0.37, 0.33, 0.26, 0.18, 0.09, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.03, 0.13, 0.19, 0.22, 0.24, 0.24, 0.23, 0.18, 0.13, 0.05, 0.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.02, 0.06, 0.08, 0.09, 0.07, 0.05, 0.01, 0.0, 0.0, 0.0, 0.0, 0.0],
+];
+
+/// Kernel profile over the normalised distance `r` in [0, 1) from the centre.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KernelShape {
+    Gaussian { sigma: f64 },
+    /// `(4r(1 - r))^alpha`, a smooth bump peaking halfway out
+    Polynomial { alpha: i32 },
+    /// `exp(alpha - alpha / (4r(1 - r)))`, the standard Lenia shell
+    Exponential { alpha: f64 },
+    /// Concentric exponential shells, one per peak, scaled by its height
+    MultiRing { peaks: Vec<f64> },
+}
+
+impl KernelShape {
+    pub fn value(&self, r: f64) -> f64 {
+        if !(0.0..1.0).contains(&r) {
+            return 0.0;
+        }
+
+        match self {
+            KernelShape::Gaussian { sigma } => (-r.powi(2) / (2.0 * sigma.powi(2))).exp(),
+            KernelShape::Polynomial { alpha } => (4.0 * r * (1.0 - r)).powi(*alpha),
+            KernelShape::Exponential { alpha } => exponential_core(r, *alpha),
+            KernelShape::MultiRing { peaks } => {
+                if peaks.is_empty() {
+                    return 0.0;
+                }
+                let scaled = r * peaks.len() as f64;
+                let ring = (scaled.floor() as usize).min(peaks.len() - 1);
+                peaks[ring] * exponential_core(scaled - ring as f64, 4.0)
+            }
+        }
+    }
+}
+
+fn exponential_core(r: f64, alpha: f64) -> f64 {
+    let bump = 4.0 * r * (1.0 - r);
+    if bump <= 0.0 {
+        0.0
+    } else {
+        (alpha - alpha / bump).exp()
+    }
+}
+
+/// Maps a neighbourhood potential to a growth rate in [-1, 1].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GrowthFunction {
+    Gaussian { mu: f64, sigma: f64 },
+    Polynomial { mu: f64, sigma: f64 },
+    /// +1 within `sigma` of `mu`, -1 elsewhere
+    Step { mu: f64, sigma: f64 },
+}
+
+impl GrowthFunction {
+    pub fn apply(&self, u: f64) -> f64 {
+        match *self {
+            GrowthFunction::Gaussian { mu, sigma } => {
+                2.0 * (-(u - mu).powi(2) / (2.0 * sigma.powi(2))).exp() - 1.0
+            }
+            GrowthFunction::Polynomial { mu, sigma } => {
+                2.0 * (1.0 - (u - mu).powi(2) / (9.0 * sigma.powi(2))).max(0.0).powi(4) - 1.0
+            }
+            GrowthFunction::Step { mu, sigma } => {
+                if (u - mu).abs() <= sigma { 1.0 } else { -1.0 }
+            }
+        }
+    }
+}
+
+/// A kernel reading the `source` channel and feeding growth into `target`, as in
+/// multi-channel Lenia. Each target channel moves by the weighted mean of the
+/// growth of every kernel aimed at it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChannelKernel {
+    pub source: usize,
+    pub target: usize,
+    pub kernel_radius: f64,
+    pub shape: KernelShape,
+    pub growth: GrowthFunction,
+    pub weight: f64,
+}
+
+/// What happens at the grid edges. Periodic wraps around; reflective mirrors
+/// the field back in; absorbing treats everything outside as empty, so mass
+/// that drifts off the edge is lost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Boundary {
+    #[default]
+    Periodic,
+    Reflective,
+    Absorbing,
+}
+
+/// Maps colony coordinates onto the grid: `origin` lands on the centre of
+/// cell (0, 0, 0) and one world unit spans `scale` cells.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridTransform {
+    pub origin: [f64; 3],
+    pub scale: f64,
+}
+
+impl Default for GridTransform {
+    fn default() -> Self {
+        Self { origin: [0.0; 3], scale: 1.0 }
+    }
+}
+
+impl GridTransform {
+    /// A transform putting the box from `min` to `max` inside a grid of
+    /// `shape`, with half a cell to spare on each side.
+    pub fn fitting(min: [f64; 3], max: [f64; 3], shape: [usize; 3]) -> Self {
+        let scale = [0, 1, 2]
+            .into_iter()
+            .filter(|&axis| shape[axis] > 1 && max[axis] > min[axis])
+            .map(|axis| (shape[axis] - 1) as f64 / (max[axis] - min[axis]))
+            .fold(f64::INFINITY, f64::min);
+        let scale = if scale.is_finite() { scale } else { 1.0 };
+        Self { origin: min, scale }
+    }
+
+    /// Continuous grid coordinates; whole numbers are cell centres.
+    pub fn to_grid(self, position: &Coordinates) -> [f64; 3] {
+        let world = [position.x, position.y, position.z];
+        [0, 1, 2].map(|axis| (world[axis] - self.origin[axis]) * self.scale)
+    }
+
+    pub fn to_world(self, grid: [f64; 3]) -> Coordinates {
+        let [x, y, z] = [0, 1, 2].map(|axis| self.origin[axis] + grid[axis] / self.scale);
+        Coordinates { x, y, z, ..Default::default() }
+    }
+
+    /// The corners of the world-space box covered by a grid of `shape`.
+    pub fn bounds(self, shape: [usize; 3]) -> ([f64; 3], [f64; 3]) {
+        let max = self.to_world(shape.map(|n| n as f64 - 1.0));
+        (self.origin, [max.x, max.y, max.z])
+    }
+}
+
+/// Starting patterns to place in an empty world.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SeedPattern {
+    Blob { radius: usize, amplitude: f64 },
+    Shell { radius: usize, width: usize, amplitude: f64 },
+    /// Uniform noise inside a ball, reproducible from `seed`
+    Noise { radius: usize, density: f64, seed: u64 },
+    /// Explicit values laid out in the x-y plane, rows along x
+    Cells { cells: Vec<Vec<f64>> },
+}
+
+impl SeedPattern {
+    pub fn render(&self) -> Array3<f64> {
+        match *self {
+            SeedPattern::Blob { radius, amplitude } => blob_pattern(radius, amplitude),
+            SeedPattern::Shell { radius, width, amplitude } => ball_pattern(radius, |d| {
+                if (d - radius as f64).abs() <= width as f64 / 2.0 { amplitude } else { 0.0 }
+            }),
+            SeedPattern::Noise { radius, density, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                ball_pattern(radius, |d| {
+                    let value = rng.gen::<f64>();
+                    if d <= radius as f64 && value < density { value / density } else { 0.0 }
+                })
+            }
+            SeedPattern::Cells { ref cells } => {
+                let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+                Array3::from_shape_fn((cells.len(), columns, 1), |(x, y, _)| cells[x].get(y).copied().unwrap_or(0.0))
+            }
+        }
+    }
+}
+
+/// One FFT plan per grid axis.
+type AxisPlans = Vec<Arc<dyn Fft<f64>>>;
+
 #[derive(Clone)]
 pub struct LeniaWorld {
-    pub grid: Array3<f64>,
+    pub channels: Vec<Array3<f64>>,
     pub params: LeniaParams,
-    kernel: Array3<f64>,
+    kernels: Vec<ChannelKernel>,
+    // Kernel transforms, computed once so each step is FFTs and products
+    kernel_ffts: Vec<Array3<Complex64>>,
+    fft_forward: AxisPlans,
+    fft_inverse: AxisPlans,
+    // Cells added on each side of every axis before convolving, for
+    // non-periodic boundaries
+    padding: [usize; 3],
 }
 
 impl LeniaWorld {
     pub fn new(params: LeniaParams) -> Self {
-        let size = params.grid_size;
-        let grid = Array3::zeros((size, size, size));
-        let kernel = Self::create_kernel(&params);
+        let shape = params.shape();
+        let kernels = params.channel_kernels();
+        let channel_count = kernels
+            .iter()
+            .map(|k| k.source.max(k.target) + 1)
+            .fold(params.channels.max(1), usize::max);
+        let channels = vec![Array3::zeros(shape); channel_count];
+
+        // Padding by the widest kernel reach keeps the FFT's wraparound out of the grid
+        let reach = kernels.iter().map(|k| k.kernel_radius.ceil() as usize).max().unwrap_or(0);
+        let padding = shape.map(|n| if params.boundary == Boundary::Periodic || n == 1 { 0 } else { reach });
+        let padded = [0, 1, 2].map(|axis| shape[axis] + 2 * padding[axis]);
+
+        let mut planner = FftPlanner::new();
+        let fft_forward: AxisPlans = padded.iter().map(|&n| planner.plan_fft_forward(n)).collect();
+        let fft_inverse: AxisPlans = padded.iter().map(|&n| planner.plan_fft_inverse(n)).collect();
+
+        let kernel_ffts = kernels
+            .iter()
+            .map(|k| {
+                let mut kernel_fft = Self::create_kernel(padded, k).mapv(Complex64::from);
+                transform(&mut kernel_fft, &fft_forward);
+                kernel_fft
+            })
+            .collect();
         
         Self {
-            grid,
+            channels,
             params,
-            kernel,
+            kernels,
+            kernel_ffts,
+            fft_forward,
+            fft_inverse,
+            padding,
         }
     }
 
-    fn create_kernel(params: &LeniaParams) -> Array3<f64> {
-        let size = params.grid_size;
-        let mut kernel = Array3::zeros((size, size, size));
-        let center = size / 2;
+    /// A world with `seed` placed at the centre of channel 0.
+    pub fn seeded(params: LeniaParams, seed: &SeedPattern) -> Self {
+        let mut world = Self::new(params);
+        let centre = world.params.shape().map(|n| (n / 2) as f64);
+        let position = world.params.transform.to_world(centre);
+        world.add_pattern(&seed.render(), &position);
+        world
+    }
+
+    /// The first channel; single-channel worlds have only this one.
+    pub fn grid(&self) -> &Array3<f64> {
+        &self.channels[0]
+    }
+
+    /// Builds the kernel with its centre at index 0 and negative offsets wrapped
+    /// to the far end, the layout periodic FFT convolution expects. Axes one
+    /// cell deep are left out, so a 2D world gets a flat kernel.
+    fn create_kernel(shape: [usize; 3], kernel: &ChannelKernel) -> Array3<f64> {
+        let mut values = Array3::zeros(shape);
+        let radius = kernel.kernel_radius.ceil() as isize;
+        let reach = shape.map(|n| if n == 1 { 0 } else { radius });
+        let wrap = |offset: isize, axis: usize| offset.rem_euclid(shape[axis] as isize) as usize;
         
-        for x in 0..size {
-            for y in 0..size {
-                for z in 0..size {
-                    let dx = (x as f64 - center as f64) / params.kernel_radius;
-                    let dy = (y as f64 - center as f64) / params.kernel_radius;
-                    let dz = (z as f64 - center as f64) / params.kernel_radius;
-                    let distance = (dx * dx + dy * dy + dz * dz).sqrt();
-                    
-                    if distance < 1.0 {
-                        kernel[[x, y, z]] = (-distance.powi(2) / 
-                            (2.0 * params.kernel_sigma.powi(2))).exp();
-                    }
+        for dx in -reach[0]..=reach[0] {
+            for dy in -reach[1]..=reach[1] {
+                for dz in -reach[2]..=reach[2] {
+                    let distance = ((dx * dx + dy * dy + dz * dz) as f64).sqrt() / kernel.kernel_radius;
+                    values[[wrap(dx, 0), wrap(dy, 1), wrap(dz, 2)]] += kernel.shape.value(distance);
                 }
             }
         }
         
         // Normalize kernel
-        let sum = kernel.sum();
-        kernel.mapv_inplace(|x| x / sum);
-        kernel
+        let sum = values.sum();
+        if sum > 0.0 {
+            values.mapv_inplace(|x| x / sum);
+        }
+        values
     }
 
-    fn growth_function(&self, u: f64) -> f64 {
-        let normalized_u = (u - self.params.growth_mu) / self.params.growth_sigma;
-        2.0 * (-normalized_u.powi(2)).exp() - 1.0
+    /// Neighbourhood potentials, one per kernel: its source channel convolved
+    /// with it under the world's boundary condition.
+    pub fn potentials(&self) -> Vec<Array3<f64>> {
+        let mut source_ffts: Vec<Option<Array3<Complex64>>> = vec![None; self.channels.len()];
+        for kernel in &self.kernels {
+            if source_ffts[kernel.source].is_none() {
+                let mut field = self.padded(&self.channels[kernel.source]);
+                transform(&mut field, &self.fft_forward);
+                source_ffts[kernel.source] = Some(field);
+            }
+        }
+
+        self.kernels
+            .iter()
+            .zip(&self.kernel_ffts)
+            .map(|(kernel, kernel_fft)| {
+                let field = source_ffts[kernel.source].clone().unwrap_or_default();
+                self.inverse_product(field, kernel_fft)
+            })
+            .collect()
     }
 
-    pub fn step(&mut self) {
-        let mut new_grid = self.grid.clone();
-        
-        // Compute convolution for each cell
-        for x in 0..self.params.grid_size {
-            for y in 0..self.params.grid_size {
-                for z in 0..self.params.grid_size {
-                    let mut sum = 0.0;
-                    
-                    // Apply kernel
-                    for kx in 0..self.params.grid_size {
-                        for ky in 0..self.params.grid_size {
-                            for kz in 0..self.params.grid_size {
-                                let gx = (x + kx) % self.params.grid_size;
-                                let gy = (y + ky) % self.params.grid_size;
-                                let gz = (z + kz) % self.params.grid_size;
-                                
-                                sum += self.grid[[gx, gy, gz]] * 
-                                      self.kernel[[kx, ky, kz]];
-                            }
-                        }
+    /// `channel` convolved with the shape and radius of `kernel`, leaving the
+    /// field as it is. Off periodic worlds the kernel should reach no further
+    /// than the world's own kernels.
+    pub fn convolve(&self, channel: usize, kernel: &ChannelKernel) -> Array3<f64> {
+        let mut field = self.padded(&self.channels[channel]);
+        let padded = [0, 1, 2].map(|axis| field.shape()[axis]);
+        let mut kernel_fft = Self::create_kernel(padded, kernel).mapv(Complex64::from);
+        transform(&mut kernel_fft, &self.fft_forward);
+        transform(&mut field, &self.fft_forward);
+        self.inverse_product(field, &kernel_fft)
+    }
+
+    // Multiplies two transforms and returns the real, unpadded result
+    fn inverse_product(&self, mut field: Array3<Complex64>, kernel_fft: &Array3<Complex64>) -> Array3<f64> {
+        Zip::from(&mut field).and(kernel_fft).par_for_each(|f, k| *f *= k);
+        transform(&mut field, &self.fft_inverse);
+
+        let scale = 1.0 / field.len() as f64;
+        let [px, py, pz] = self.padding;
+        let [nx, ny, nz] = self.params.shape();
+        field
+            .slice(s![px..px + nx, py..py + ny, pz..pz + nz])
+            .mapv(|c| c.re * scale)
+    }
+
+    // The field surrounded by `padding` cells filled according to the boundary
+    fn padded(&self, field: &Array3<f64>) -> Array3<Complex64> {
+        if self.padding == [0; 3] {
+            return field.mapv(Complex64::from);
+        }
+
+        let shape = self.params.shape();
+        let padded = [0, 1, 2].map(|axis| shape[axis] + 2 * self.padding[axis]);
+        let reflective = self.params.boundary == Boundary::Reflective;
+        // Index into the grid for a padded position, or None outside an absorbing edge
+        let source = |index: usize, axis: usize| -> Option<usize> {
+            let n = shape[axis] as isize;
+            let offset = index as isize - self.padding[axis] as isize;
+            if (0..n).contains(&offset) {
+                Some(offset as usize)
+            } else if reflective {
+                let mirrored = offset.rem_euclid(2 * n);
+                Some(if mirrored < n { mirrored } else { 2 * n - 1 - mirrored } as usize)
+            } else {
+                None
+            }
+        };
+
+        Array3::from_shape_fn(padded, |(x, y, z)| {
+            match (source(x, 0), source(y, 1), source(z, 2)) {
+                (Some(x), Some(y), Some(z)) => Complex64::from(field[[x, y, z]]),
+                _ => Complex64::default(),
+            }
+        })
+    }
+
+    // Grid cell for a position: wrapped on periodic worlds, clamped to the edge otherwise
+    fn cell_index(&self, x: isize, y: isize, z: isize) -> Option<[usize; 3]> {
+        let shape = self.params.shape();
+        let mut index = [0; 3];
+        for (axis, value) in [x, y, z].into_iter().enumerate() {
+            let n = shape[axis] as isize;
+            index[axis] = if self.params.boundary == Boundary::Periodic {
+                value.rem_euclid(n) as usize
+            } else if (0..n).contains(&value) {
+                value as usize
+            } else {
+                return None;
+            };
+        }
+        Some(index)
+    }
+
+    // The grid cells around a continuous grid position with their trilinear
+    // weights. Flat axes always use their single cell; corners off a
+    // non-periodic grid are left out.
+    fn corners(&self, grid: [f64; 3]) -> Vec<([usize; 3], f64)> {
+        let shape = self.params.shape();
+        let axis_terms = |axis: usize| -> Vec<(isize, f64)> {
+            if shape[axis] == 1 {
+                return vec![(0, 1.0)];
+            }
+            let base = grid[axis].floor();
+            let t = grid[axis] - base;
+            vec![(base as isize, 1.0 - t), (base as isize + 1, t)]
+        };
+
+        let mut corners = Vec::with_capacity(8);
+        for &(x, wx) in &axis_terms(0) {
+            for &(y, wy) in &axis_terms(1) {
+                for &(z, wz) in &axis_terms(2) {
+                    let weight = wx * wy * wz;
+                    if weight <= 0.0 {
+                        continue;
+                    }
+                    if let Some(index) = self.cell_index(x, y, z) {
+                        corners.push((index, weight));
                     }
-                    
-                    // Apply growth function and update
-                    let growth = self.growth_function(sum);
-                    new_grid[[x, y, z]] = (self.grid[[x, y, z]] + 
-                        self.params.dt * growth).clamp(0.0, 1.0);
                 }
             }
         }
-        
-        self.grid = new_grid;
+        corners
     }
 
+    pub fn step(&mut self) {
+        let potentials = self.potentials();
+        let channel_count = self.channels.len();
+        let shape = self.channels[0].raw_dim();
+
+        let mut growth = vec![Array3::<f64>::zeros(shape); channel_count];
+        let mut weights = vec![0.0; channel_count];
+        for (kernel, potential) in self.kernels.iter().zip(&potentials) {
+            Zip::from(&mut growth[kernel.target]).and(potential).par_for_each(|g, &u| {
+                *g += kernel.weight * kernel.growth.apply(u);
+            });
+            weights[kernel.target] += kernel.weight;
+        }
+
+        let dt = self.params.dt;
+        for ((channel, growth), weight) in self.channels.iter_mut().zip(&growth).zip(weights) {
+            // Channels no kernel targets are left as they are
+            if weight <= 0.0 {
+                continue;
+            }
+            Zip::from(channel).and(growth).par_for_each(|cell, &g| {
+                *cell = (*cell + dt * g / weight).clamp(0.0, 1.0);
+            });
+        }
+    }
+
+    /// Adds `pattern` centred on `position`. Deposits accumulate instead of
+    /// overwriting what is already in the field, and saturate at 1.0 on
+    /// channels a kernel evolves.
     pub fn add_pattern(&mut self, pattern: &Array3<f64>, position: &Coordinates) {
-        let px = position.x.round() as usize % self.params.grid_size;
-        let py = position.y.round() as usize % self.params.grid_size;
-        let pz = position.z.round() as usize % self.params.grid_size;
-        
-        let pattern_size = pattern.shape()[0];
-        for x in 0..pattern_size {
-            for y in 0..pattern_size {
-                for z in 0..pattern_size {
-                    let gx = (px + x) % self.params.grid_size;
-                    let gy = (py + y) % self.params.grid_size;
-                    let gz = (pz + z) % self.params.grid_size;
-                    self.grid[[gx, gy, gz]] = pattern[[x, y, z]];
-                }
+        self.add_pattern_to(0, pattern, position);
+    }
+
+    /// `pattern` is in grid cells. Each of its cells is splatted onto the
+    /// grid cells around it, so off-grid positions deposit smoothly. On flat
+    /// axes only the pattern's middle slice is used; on non-periodic worlds
+    /// the parts that fall outside the grid are dropped. Channels no kernel
+    /// targets are passive and accumulate without saturating.
+    pub fn add_pattern_to(&mut self, channel: usize, pattern: &Array3<f64>, position: &Coordinates) {
+        let shape = self.params.shape();
+        let centre = self.params.transform.to_grid(position);
+        let extent = pattern.shape();
+
+        let mut deposits: Vec<([usize; 3], f64)> = Vec::new();
+        for ((x, y, z), &value) in pattern.indexed_iter() {
+            let offsets = [x, y, z];
+            if value == 0.0 || (0..3).any(|axis| shape[axis] == 1 && offsets[axis] != extent[axis] / 2) {
+                continue;
             }
+            let target = [0, 1, 2].map(|axis| centre[axis] + offsets[axis] as f64 - (extent[axis] / 2) as f64);
+            deposits.extend(self.corners(target).into_iter().map(|(index, weight)| (index, weight * value)));
         }
+
+        let limit = if self.kernels.iter().any(|k| k.target == channel) { 1.0 } else { f64::INFINITY };
+        let grid = &mut self.channels[channel];
+        for (index, amount) in deposits {
+            grid[index] = (grid[index] + amount).min(limit);
+        }
+    }
+
+    /// Switches to `transform`, resampling every channel so the field stays
+    /// where it was in world space. Regions the old grid did not cover start
+    /// empty, even on periodic worlds.
+    pub fn retarget(&mut self, transform: GridTransform) {
+        let shape = self.params.shape();
+        let covered = |position: &Coordinates| {
+            let grid = self.params.transform.to_grid(position);
+            (0..3).all(|axis| shape[axis] == 1 || (0.0..=(shape[axis] - 1) as f64).contains(&grid[axis]))
+        };
+        let resampled: Vec<Array3<f64>> = (0..self.channels.len())
+            .map(|channel| {
+                Array3::from_shape_fn(shape, |(x, y, z)| {
+                    let position = transform.to_world([x as f64, y as f64, z as f64]);
+                    if covered(&position) { self.get_channel_state_at(channel, &position) } else { 0.0 }
+                })
+            })
+            .collect();
+        self.channels = resampled;
+        self.params.transform = transform;
     }
 
     pub fn get_state_at(&self, position: &Coordinates) -> f64 {
-        let x = position.x.round() as usize % self.params.grid_size;
-        let y = position.y.round() as usize % self.params.grid_size;
-        let z = position.z.round() as usize % self.params.grid_size;
-        self.grid[[x, y, z]]
+        self.get_channel_state_at(0, position)
+    }
+
+    /// Trilinear sample at `position`. Off a non-periodic grid the field reads
+    /// as empty; flat axes ignore the position's coordinate along them.
+    pub fn get_channel_state_at(&self, channel: usize, position: &Coordinates) -> f64 {
+        self.sample(&self.channels[channel], position)
+    }
+
+    /// Trilinear sample of any grid-shaped `field`, such as a convolved channel.
+    pub fn sample(&self, field: &Array3<f64>, position: &Coordinates) -> f64 {
+        self.corners(self.params.transform.to_grid(position))
+            .into_iter()
+            .map(|(index, weight)| weight * field[index])
+            .sum()
+    }
+
+    pub fn to_state(&self) -> LeniaWorldState {
+        LeniaWorldState {
+            grid: self.channels.iter().flat_map(|c| c.iter().copied()).collect(),
+            size: self.params.grid_size,
+            growth_mu: self.params.growth_mu,
+            growth_sigma: self.params.growth_sigma,
+            channels: self.channels.len(),
+            extent: self.params.extent,
+        }
+    }
+
+    pub fn restore_state(&mut self, state: &LeniaWorldState) -> Result<(), Box<dyn Error>> {
+        let shape = self.params.shape();
+        let saved_shape = state.extent.unwrap_or([state.size; 3]);
+        if saved_shape != shape {
+            return Err(format!("Saved Lenia grid {:?} does not match {:?}", saved_shape, shape).into());
+        }
+
+        // States saved before channels existed hold a single grid
+        let saved_channels = state.channels.max(1);
+        if saved_channels != self.channels.len() {
+            return Err(format!("Saved Lenia world has {} channels, expected {}", saved_channels, self.channels.len()).into());
+        }
+
+        let cells: usize = shape.iter().product();
+        if state.grid.len() != cells * saved_channels {
+            return Err("Saved Lenia grid has the wrong number of values".into());
+        }
+        for (channel, values) in self.channels.iter_mut().zip(state.grid.chunks(cells)) {
+            *channel = Array3::from_shape_vec(shape, values.to_vec())?;
+        }
+        // Worlds built from the scalar fields keep their saved growth parameters
+        if self.params.kernels.is_empty() {
+            self.params.growth_mu = state.growth_mu;
+            self.params.growth_sigma = state.growth_sigma;
+            self.kernels = self.params.channel_kernels();
+        }
+        Ok(())
+    }
+}
+
+/// A ball of the given radius whose values fall off linearly from `amplitude`
+/// at the centre to zero at the edge.
+pub fn blob_pattern(radius: usize, amplitude: f64) -> Array3<f64> {
+    ball_pattern(radius, |d| (amplitude * (1.0 - d / (radius as f64 + 1.0))).max(0.0))
+}
+
+// A cube just large enough for a ball of `radius`, filled by distance from its centre
+fn ball_pattern(radius: usize, mut value: impl FnMut(f64) -> f64) -> Array3<f64> {
+    let size = radius * 2 + 1;
+    let mut pattern = Array3::zeros((size, size, size));
+    for ((x, y, z), cell) in pattern.indexed_iter_mut() {
+        let d = [x, y, z]
+            .iter()
+            .map(|&i| (i as f64 - radius as f64).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        *cell = value(d);
+    }
+    pattern
+}
+
+// In-place multidimensional FFT: a 1D transform along every lane of each axis
+fn transform(data: &mut Array3<Complex64>, plans: &[Arc<dyn Fft<f64>>]) {
+    for (axis, fft) in plans.iter().enumerate() {
+        Zip::from(data.lanes_mut(Axis(axis))).par_for_each(|mut lane| {
+            let mut buffer: Vec<Complex64> = lane.iter().copied().collect();
+            fft.process(&mut buffer);
+            lane.iter_mut().zip(buffer).for_each(|(dst, src)| *dst = src);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_params() -> LeniaParams {
+        LeniaParams {
+            kernel_radius: 2.0,
+            grid_size: 8,
+            ..LeniaParams::default()
+        }
+    }
+
+    #[test]
+    fn test_potential_matches_direct_convolution() {
+        let mut world = LeniaWorld::new(small_params());
+        let n = world.params.grid_size;
+        for (i, cell) in world.channels[0].iter_mut().enumerate() {
+            *cell = ((i * 7919) % 13) as f64 / 13.0;
+        }
+
+        let potential = world.potentials().swap_remove(0);
+        let kernel = LeniaWorld::create_kernel([n; 3], &world.params.channel_kernels()[0]);
+        for &(x, y, z) in &[(0, 0, 0), (3, 5, 7), (7, 1, 4)] {
+            let mut expected = 0.0;
+            for kx in 0..n {
+                for ky in 0..n {
+                    for kz in 0..n {
+                        let g = world.grid()[[(x + n - kx) % n, (y + n - ky) % n, (z + n - kz) % n]];
+                        expected += g * kernel[[kx, ky, kz]];
+                    }
+                }
+            }
+            assert!((potential[[x, y, z]] - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_step_keeps_field_in_unit_range() {
+        let mut world = LeniaWorld::new(small_params());
+        world.channels[0][[4, 4, 4]] = 1.0;
+        world.step();
+
+        assert!(world.grid().iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!((LeniaWorld::create_kernel([8; 3], &world.params.channel_kernels()[0]).sum() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_add_pattern_centres_and_accumulates() {
+        let mut world = LeniaWorld::new(small_params());
+        let position = Coordinates { x: 0.0, y: 3.0, z: 3.0, ..Default::default() };
+        let pattern = blob_pattern(1, 0.6);
+
+        world.add_pattern(&pattern, &position);
+        world.add_pattern(&pattern, &position);
+
+        assert_eq!(world.get_state_at(&position), 1.0);
+        assert!(world.grid()[[7, 3, 3]] > 0.0);
+        assert_eq!(world.grid()[[2, 3, 3]], 0.0);
+    }
+
+    #[test]
+    fn test_cross_channel_kernel_feeds_target_only() {
+        let mut world = LeniaWorld::new(LeniaParams {
+            channels: 2,
+            kernels: vec![ChannelKernel {
+                source: 0,
+                target: 1,
+                kernel_radius: 2.0,
+                shape: KernelShape::Gaussian { sigma: 3.0 },
+                growth: GrowthFunction::Step { mu: 0.5, sigma: 0.1 },
+                weight: 1.0,
+            }],
+            ..small_params()
+        });
+        world.channels[0].fill(0.5);
+        world.step();
+
+        assert!(world.channels[0].iter().all(|&v| v == 0.5));
+        assert!(world.channels[1].iter().all(|&v| (v - world.params.dt).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_presets_round_trip_through_json() {
+        for name in PRESETS {
+            let (params, seed) = LeniaParams::preset(name).unwrap();
+            let json = serde_json::to_string(&params).unwrap();
+            let parsed: LeniaParams = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(parsed.channel_kernels().len(), params.channel_kernels().len());
+            assert!(seed.render().iter().any(|&v| v > 0.0));
+        }
+        assert!(LeniaParams::preset("unknown").is_none());
+    }
+
+    #[test]
+    fn test_orbium_preset_persists() {
+        let (params, seed) = LeniaParams::preset("orbium").unwrap();
+        let mut world = LeniaWorld::seeded(params, &seed);
+        let initial = world.grid().clone();
+        let start = initial.sum();
+        for _ in 0..200 {
+            world.step();
+        }
+        let mass = world.grid().sum();
+        assert!(mass > 0.7 * start && mass < 1.3 * start, "{} -> {}", start, mass);
+
+        // It glides, so little of it still overlaps where it started
+        let overlap: f64 = initial.iter().zip(world.grid()).map(|(a, b)| a.min(*b)).sum();
+        assert!(overlap < 0.5 * start, "{}", overlap);
+    }
+
+    #[test]
+    fn test_kernel_shapes_vanish_at_the_edge() {
+        let shapes = [
+            KernelShape::Polynomial { alpha: 4 },
+            KernelShape::Exponential { alpha: 4.0 },
+            KernelShape::MultiRing { peaks: vec![0.5, 1.0] },
+        ];
+        for shape in &shapes {
+            assert_eq!(shape.value(0.0), 0.0);
+            assert_eq!(shape.value(1.0), 0.0);
+        }
+        assert!(shapes[0].value(0.5) > 0.0 && shapes[1].value(0.5) > 0.0);
+        // Each ring peaks at its own centre and falls to zero where rings meet
+        let rings = &shapes[2];
+        assert_eq!(rings.value(0.25), 0.5);
+        assert_eq!(rings.value(0.75), 1.0);
+        assert_eq!(rings.value(0.5), 0.0);
+        assert_eq!(GrowthFunction::Gaussian { mu: 0.15, sigma: 0.015 }.apply(0.15), 1.0);
+        assert_eq!(GrowthFunction::Step { mu: 0.2, sigma: 0.05 }.apply(0.5), -1.0);
+    }
+
+    #[test]
+    fn test_planar_world_uses_flat_kernel() {
+        let params = LeniaParams { kernel_radius: 2.0, ..LeniaParams::planar(8, 6) };
+        let kernel = LeniaWorld::create_kernel(params.shape(), &params.channel_kernels()[0]);
+        assert_eq!(kernel.shape(), &[8, 6, 1]);
+        assert!((kernel.sum() - 1.0).abs() < 1e-9);
+
+        let mut world = LeniaWorld::new(params);
+        let position = Coordinates { x: 2.0, y: 3.0, z: 5.0, ..Default::default() };
+        world.add_pattern(&blob_pattern(1, 0.5), &position);
+        assert_eq!(world.get_state_at(&position), 0.5);
+        world.step();
+        assert_eq!(world.grid().shape(), &[8, 6, 1]);
+    }
+
+    #[test]
+    fn test_boundaries_differ_at_the_edge() {
+        let potential_at_corner = |boundary| {
+            let mut world = LeniaWorld::new(LeniaParams { boundary, ..small_params() });
+            world.channels[0].fill(1.0);
+            world.potentials().swap_remove(0)[[0, 0, 0]]
+        };
+
+        // A full field looks full from everywhere unless the outside counts as empty
+        assert!((potential_at_corner(Boundary::Periodic) - 1.0).abs() < 1e-9);
+        assert!((potential_at_corner(Boundary::Reflective) - 1.0).abs() < 1e-9);
+        assert!(potential_at_corner(Boundary::Absorbing) < 0.9);
+    }
+
+    #[test]
+    fn test_fractional_and_negative_positions_interpolate() {
+        let mut world = LeniaWorld::new(small_params());
+        world.channels[0][[7, 4, 1]] = 1.0;
+
+        // x = -0.3 sits between cell 7 (wrapped) and cell 0
+        let position = Coordinates { x: -0.3, y: 4.0, z: 1.0, ..Default::default() };
+        assert!((world.get_state_at(&position) - 0.3).abs() < 1e-9);
+
+        let mut world = LeniaWorld::new(small_params());
+        let position = Coordinates { x: -0.3, y: 4.7, z: 1.2, ..Default::default() };
+        world.add_pattern(&blob_pattern(0, 0.5), &position);
+        assert!((world.grid().sum() - 0.5).abs() < 1e-9);
+        assert!(world.grid()[[7, 5, 1]] > world.grid()[[0, 4, 2]]);
+    }
+
+    #[test]
+    fn test_retarget_leaves_new_region_empty() {
+        let mut world = LeniaWorld::new(small_params());
+        world.channels[0].fill(1.0);
+        world.retarget(GridTransform::fitting([0.0; 3], [14.0; 3], [8; 3]));
+
+        // Grid cell 3 maps back to world 6, inside the old 0..7 box; cell 4 to world 8
+        assert_eq!(world.grid()[[3, 3, 3]], 1.0);
+        assert_eq!(world.grid()[[4, 3, 3]], 0.0);
+        assert_eq!(world.grid()[[7, 7, 7]], 0.0);
+    }
+
+    #[test]
+    fn test_transform_fits_bounds() {
+        let transform = GridTransform::fitting([-2.0, 0.0, 0.0], [6.0, 4.0, 4.0], [9, 9, 9]);
+        let grid = transform.to_grid(&Coordinates { x: 6.0, y: 0.0, z: 4.0, ..Default::default() });
+        assert_eq!(grid, [8.0, 0.0, 4.0]);
+
+        let (min, max) = transform.bounds([9, 9, 9]);
+        assert_eq!(min, [-2.0, 0.0, 0.0]);
+        assert_eq!(max, [6.0, 8.0, 8.0]);
     }
 }
@@ -0,0 +1,332 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::types::DimensionalPosition;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LineageRecord {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    // Second parent of a crossover child; the tree follows `parent_id` only
+    #[serde(default)]
+    pub co_parent_id: Option<Uuid>,
+    pub children: Vec<Uuid>,
+    pub generation: u32,
+    pub birth_cycle: u64,
+    pub death_cycle: Option<u64>,
+    pub dna_at_birth: DimensionalPosition,
+    pub best_plan_score: f64,
+}
+
+/// The colony's family tree. Records are kept after death so lineages stay
+/// complete.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Lineage {
+    records: HashMap<Uuid, LineageRecord>,
+}
+
+impl Lineage {
+    pub fn record_birth(
+        &mut self,
+        id: Uuid,
+        parent_id: Option<Uuid>,
+        co_parent_id: Option<Uuid>,
+        dna: &DimensionalPosition,
+        cycle: u64,
+    ) {
+        let parent = parent_id.and_then(|parent| self.records.get_mut(&parent));
+        let generation = match parent {
+            Some(parent) => {
+                parent.children.push(id);
+                parent.generation + 1
+            }
+            None => 0,
+        };
+
+        self.records.insert(id, LineageRecord {
+            id,
+            parent_id,
+            co_parent_id,
+            children: Vec::new(),
+            generation,
+            birth_cycle: cycle,
+            death_cycle: None,
+            dna_at_birth: dna.clone(),
+            best_plan_score: 0.0,
+        });
+    }
+
+    pub fn record_death(&mut self, id: Uuid, cycle: u64) {
+        if let Some(record) = self.records.get_mut(&id) {
+            record.death_cycle = Some(cycle);
+        }
+    }
+
+    pub fn record_plan_score(&mut self, id: Uuid, score: f64) {
+        if let Some(record) = self.records.get_mut(&id) {
+            record.best_plan_score = record.best_plan_score.max(score);
+        }
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&LineageRecord> {
+        self.records.get(id)
+    }
+
+    /// Parent first, then grandparent, up to the founding cell.
+    pub fn ancestors(&self, id: Uuid) -> Vec<Uuid> {
+        let mut ancestors = Vec::new();
+        let mut current = self.records.get(&id).and_then(|r| r.parent_id);
+        while let Some(parent) = current {
+            // A malformed cycle in loaded data must not loop forever
+            if ancestors.contains(&parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = self.records.get(&parent).and_then(|r| r.parent_id);
+        }
+        ancestors
+    }
+
+    /// Every cell descended from `id`, breadth first.
+    pub fn descendants(&self, id: Uuid) -> Vec<Uuid> {
+        let mut descendants = Vec::new();
+        let mut queue: VecDeque<Uuid> = self.children(id).into();
+        while let Some(next) = queue.pop_front() {
+            descendants.push(next);
+            queue.extend(self.children(next));
+        }
+        descendants
+    }
+
+    fn children(&self, id: Uuid) -> Vec<Uuid> {
+        self.records.get(&id).map(|r| r.children.clone()).unwrap_or_default()
+    }
+
+    /// How far a cell's Thought DNA at birth sits from its parent's.
+    pub fn dna_drift(&self, id: Uuid) -> f64 {
+        let Some(record) = self.records.get(&id) else { return 0.0 };
+        match record.parent_id.and_then(|parent| self.records.get(&parent)) {
+            Some(parent) => dna_distance(&record.dna_at_birth, &parent.dna_at_birth),
+            None => 0.0,
+        }
+    }
+
+    /// Drift from the founding ancestor, summed over every generation in between.
+    pub fn total_dna_drift(&self, id: Uuid) -> f64 {
+        std::iter::once(id)
+            .chain(self.ancestors(id))
+            .map(|cell| self.dna_drift(cell))
+            .sum()
+    }
+
+    /// The founding cell whose family has the highest summed best plan score.
+    pub fn most_successful_lineage(&self) -> Option<(Uuid, f64)> {
+        self.roots()
+            .into_iter()
+            .map(|root| {
+                let score = std::iter::once(root)
+                    .chain(self.descendants(root))
+                    .filter_map(|id| self.records.get(&id))
+                    .map(|r| r.best_plan_score)
+                    .sum::<f64>();
+                (root, score)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    // Founding cells, oldest first so exports are stable
+    fn roots(&self) -> Vec<Uuid> {
+        let mut roots: Vec<&LineageRecord> = self.records.values()
+            .filter(|r| !r.parent_id.is_some_and(|parent| self.records.contains_key(&parent)))
+            .collect();
+        roots.sort_by_key(|r| (r.birth_cycle, r.id));
+        roots.into_iter().map(|r| r.id).collect()
+    }
+
+    /// Newick tree with one subtree per founding cell. Labels are short cell
+    /// IDs and branch lengths are the DNA drift from the parent.
+    pub fn to_newick(&self) -> String {
+        let trees: Vec<String> = self.roots().into_iter().map(|root| self.newick_subtree(root)).collect();
+        match trees.len() {
+            1 => format!("{};", trees[0]),
+            _ => format!("({});", trees.join(",")),
+        }
+    }
+
+    // Written front to back from an explicit stack, so a lineage thousands of
+    // generations deep neither overflows the call stack nor copies subtrees
+    fn newick_subtree(&self, root: Uuid) -> String {
+        enum Step {
+            Open(Uuid),
+            Comma,
+            Close(Uuid),
+        }
+
+        let label = |id: Uuid| format!("{}:{:.3}", short_id(id), self.dna_drift(id));
+        let mut newick = String::new();
+        // A malformed cycle in loaded data must not loop forever
+        let mut visited: HashSet<Uuid> = HashSet::from([root]);
+        let mut stack = vec![Step::Open(root)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Open(id) => {
+                    let children: Vec<Uuid> = self.sorted_children(id).into_iter().filter(|child| visited.insert(*child)).collect();
+                    if children.is_empty() {
+                        newick.push_str(&label(id));
+                        continue;
+                    }
+                    newick.push('(');
+                    stack.push(Step::Close(id));
+                    for (index, child) in children.into_iter().enumerate().rev() {
+                        stack.push(Step::Open(child));
+                        if index > 0 {
+                            stack.push(Step::Comma);
+                        }
+                    }
+                }
+                Step::Comma => newick.push(','),
+                Step::Close(id) => {
+                    newick.push(')');
+                    newick.push_str(&label(id));
+                }
+            }
+        }
+        newick
+    }
+
+    // Children that have records, oldest first so exports are stable
+    fn sorted_children(&self, id: Uuid) -> Vec<Uuid> {
+        let mut children: Vec<&LineageRecord> = self.children(id)
+            .iter()
+            .filter_map(|child| self.records.get(child))
+            .collect();
+        children.sort_by_key(|r| (r.birth_cycle, r.id));
+        children.into_iter().map(|r| r.id).collect()
+    }
+
+    /// `{nodes, edges}` graph for visualization tools. Each node also carries
+    /// its total DNA drift from the founding cell.
+    pub fn to_json_graph(&self) -> Value {
+        let mut records: Vec<&LineageRecord> = self.records.values().collect();
+        records.sort_by_key(|r| (r.birth_cycle, r.id));
+
+        let nodes: Vec<Value> = records.iter()
+            .map(|r| {
+                let mut node = json!(r);
+                node["total_dna_drift"] = json!(self.total_dna_drift(r.id));
+                node
+            })
+            .collect();
+        let mut edges: Vec<Value> = records.iter()
+            .filter_map(|r| r.parent_id.filter(|p| self.records.contains_key(p)).map(|parent| json!({
+                "source": parent,
+                "target": r.id,
+                "kind": "parent",
+                "dna_drift": self.dna_drift(r.id),
+            })))
+            .collect();
+        edges.extend(records.iter().filter_map(|r| {
+            r.co_parent_id.filter(|p| self.records.contains_key(p)).map(|co_parent| json!({
+                "source": co_parent,
+                "target": r.id,
+                "kind": "co_parent",
+            }))
+        }));
+
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    pub fn export(&self, dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("lineage.nwk"), self.to_newick())?;
+        fs::write(dir.join("lineage.json"), serde_json::to_string_pretty(&self.to_json_graph())?)?;
+        Ok(())
+    }
+}
+
+fn dna_distance(a: &DimensionalPosition, b: &DimensionalPosition) -> f64 {
+    a.axes().iter()
+        .zip(b.axes())
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn short_id(id: Uuid) -> String {
+    id.simple().to_string()[..8].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dna(emergence: f64) -> DimensionalPosition {
+        DimensionalPosition {
+            emergence,
+            coherence: 0.0,
+            resilience: 0.0,
+            intelligence: 0.0,
+            efficiency: 0.0,
+            integration: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_ancestry_queries() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let mut lineage = Lineage::default();
+        lineage.record_birth(ids[0], None, None, &dna(0.0), 0);
+        lineage.record_birth(ids[1], Some(ids[0]), None, &dna(3.0), 1);
+        lineage.record_birth(ids[2], Some(ids[1]), None, &dna(7.0), 2);
+        lineage.record_birth(ids[3], None, None, &dna(50.0), 0);
+        lineage.record_plan_score(ids[2], 0.9);
+
+        assert_eq!(lineage.ancestors(ids[2]), [ids[1], ids[0]]);
+        assert_eq!(lineage.descendants(ids[0]), [ids[1], ids[2]]);
+        assert_eq!(lineage.get(&ids[2]).unwrap().generation, 2);
+        assert!((lineage.total_dna_drift(ids[2]) - 7.0).abs() < 1e-9);
+        assert_eq!(lineage.most_successful_lineage().unwrap().0, ids[0]);
+    }
+
+    #[test]
+    fn test_newick_export() {
+        let (root, child, sibling) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut lineage = Lineage::default();
+        lineage.record_birth(root, None, None, &dna(0.0), 0);
+        lineage.record_birth(child, Some(root), None, &dna(4.0), 1);
+        lineage.record_birth(sibling, Some(root), None, &dna(-2.0), 2);
+
+        let expected = format!("({}:4.000,{}:2.000){}:0.000;", short_id(child), short_id(sibling), short_id(root));
+        assert_eq!(lineage.to_newick(), expected);
+        assert_eq!(lineage.to_json_graph()["edges"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_newick_export_of_a_deep_lineage() {
+        let mut lineage = Lineage::default();
+        let mut parent = None;
+        for generation in 0..100_000 {
+            let id = Uuid::new_v4();
+            lineage.record_birth(id, parent, None, &dna(0.0), generation);
+            parent = Some(id);
+        }
+
+        let newick = lineage.to_newick();
+        assert_eq!(newick.matches('(').count(), 99_999);
+        assert!(newick.ends_with(":0.000;"));
+    }
+}
@@ -8,8 +8,11 @@ The above copyright notice and this permission notice shall be included in all c
 
 THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
 
+use crate::models::constants::LTL_RULES;
 use crate::models::types::Coordinates;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -34,7 +37,15 @@ impl ExtendedNeighborhood {
         spatial_influence * (0.7 + 0.3 * phase_sync)
     }
 
-    pub fn update_neighbors(&mut self, cell_position: &Coordinates, other_cells: &[(Uuid, Coordinates)]) {
+    /// Neighbours within `radius`, weighted by distance and by how closely their
+    /// phase in `states` matches `cell_phase`.
+    pub fn update_neighbors(
+        &mut self,
+        cell_position: &Coordinates,
+        cell_phase: f64,
+        other_cells: &[(Uuid, Coordinates)],
+        states: &HashMap<Uuid, EnhancedCellState>,
+    ) {
         let mut neighbor_distances: Vec<(Uuid, f64)> = other_cells
             .iter()
             .map(|(id, pos)| {
@@ -49,7 +60,8 @@ impl ExtendedNeighborhood {
 
         self.neighbors.clear();
         for (id, distance) in neighbor_distances {
-            let influence = self.calculate_influence(distance, 0.0, 0.0); // Default phase values for now
+            let neighbor_phase = states.get(&id).map_or(cell_phase, |state| state.phase);
+            let influence = self.calculate_influence(distance, neighbor_phase, cell_phase);
             self.neighbors.insert(id, (distance, influence));
         }
     }
@@ -79,7 +91,7 @@ impl EnhancedCellState {
         if total_weight > 0.0 {
             self.phase_velocity += self.coupling_strength * phase_diff_sum / total_weight;
             self.phase += self.phase_velocity * self.adaptation_rate;
-            self.phase = self.phase % (2.0 * std::f64::consts::PI);
+            self.phase = self.phase.rem_euclid(2.0 * std::f64::consts::PI);
             
             // Damping
             self.phase_velocity *= 0.9;
@@ -121,6 +133,15 @@ impl EnhancedCellState {
         }
     }
 
+    /// Kuramoto step: pulls the phase toward the neighbours' phases, weighted
+    /// by their influence.
+    pub fn couple_phases(&mut self, neighborhood: &ExtendedNeighborhood, neighbor_states: &HashMap<Uuid, EnhancedCellState>) {
+        let (phases, weights): (Vec<f64>, Vec<f64>) = neighborhood.neighbors.iter()
+            .filter_map(|(id, (_, weight))| neighbor_states.get(id).map(|state| (state.phase, *weight)))
+            .unzip();
+        self.calculate_phase_coupling(&phases, &weights);
+    }
+
     fn calculate_stability(&self, neighborhood: &ExtendedNeighborhood, neighbor_states: &HashMap<Uuid, EnhancedCellState>) -> f64 {
         let mut variance = 0.0;
         let mut count = 0;
@@ -141,11 +162,77 @@ impl EnhancedCellState {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum InteractionEffect {
-    EnergyBoost(f64),
+    EnergyTransfer { from: Uuid, amount: f64 },
     SynchronizationBonus(f64),
     SpawnConditionsMet,
+    SurvivalFailed,
+}
+
+/// Larger-than-Life birth and survival thresholds over a cell's neighbour
+/// count, written `B<min>-<max>/S<min>-<max>` (e.g. `B1-8/S1-12`). A cell
+/// may bud only while its count is in the birth range and loses energy
+/// each tick it is outside the survival range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtlRules {
+    pub birth: (usize, usize),
+    pub survival: (usize, usize),
+}
+
+impl LtlRules {
+    pub fn allows_birth(&self, neighbors: usize) -> bool {
+        (self.birth.0..=self.birth.1).contains(&neighbors)
+    }
+
+    pub fn allows_survival(&self, neighbors: usize) -> bool {
+        (self.survival.0..=self.survival.1).contains(&neighbors)
+    }
+}
+
+impl Default for LtlRules {
+    fn default() -> Self {
+        LTL_RULES.parse().expect("default LTL rules are valid")
+    }
+}
+
+impl FromStr for LtlRules {
+    type Err = String;
+
+    fn from_str(rules: &str) -> Result<Self, Self::Err> {
+        let parse_range = |part: Option<&str>, prefix: char| -> Result<(usize, usize), String> {
+            let part = part
+                .and_then(|p| p.trim().strip_prefix(prefix).or_else(|| p.trim().strip_prefix(prefix.to_ascii_lowercase())))
+                .ok_or_else(|| format!("LTL rules '{}' need a {} section", rules, prefix))?;
+            let (min, max) = part.split_once('-').unwrap_or((part, part));
+            let min = min.parse().map_err(|_| format!("Invalid {} threshold '{}'", prefix, min))?;
+            let max = max.parse().map_err(|_| format!("Invalid {} threshold '{}'", prefix, max))?;
+            if min > max {
+                return Err(format!("{} range {}-{} is empty", prefix, min, max));
+            }
+            Ok((min, max))
+        };
+
+        let mut parts = rules.split('/');
+        let birth = parse_range(parts.next(), 'B')?;
+        let survival = parse_range(parts.next(), 'S')?;
+        Ok(Self { birth, survival })
+    }
+}
+
+/// Kuramoto order parameter: 1.0 when every phase is equal, near 0.0 when
+/// they are spread evenly around the circle.
+pub fn phase_order(phases: impl IntoIterator<Item = f64>) -> f64 {
+    let (mut sin_sum, mut cos_sum, mut count) = (0.0, 0.0, 0);
+    for phase in phases {
+        sin_sum += phase.sin();
+        cos_sum += phase.cos();
+        count += 1;
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    (sin_sum * sin_sum + cos_sum * cos_sum).sqrt() / count as f64
 }
 
 pub fn calculate_3d_distance(a: &Coordinates, b: &Coordinates) -> f64 {
@@ -154,3 +241,48 @@ pub fn calculate_3d_distance(a: &Coordinates, b: &Coordinates) -> f64 {
     let dz = a.z - b.z;
     (dx * dx + dy * dy + dz * dz).sqrt()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coupled_phases_synchronize() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut states: HashMap<Uuid, EnhancedCellState> = ids.iter()
+            .zip([0.2, 2.0])
+            .map(|(id, phase)| (*id, EnhancedCellState { phase, ..EnhancedCellState::new() }))
+            .collect();
+        let before = phase_order(states.values().map(|s| s.phase));
+
+        for _ in 0..200 {
+            let snapshot = states.clone();
+            for (i, id) in ids.iter().enumerate() {
+                let mut neighborhood = ExtendedNeighborhood::new(3.0, 12);
+                neighborhood.neighbors.insert(ids[1 - i], (1.0, 1.0));
+                states.get_mut(id).unwrap().couple_phases(&neighborhood, &snapshot);
+            }
+        }
+
+        let after = phase_order(states.values().map(|s| s.phase));
+        assert!(before < 0.8);
+        assert!(after > 0.99);
+    }
+
+    #[test]
+    fn test_rules_parse() {
+        let rules: LtlRules = "B2-4/S1-8".parse().unwrap();
+        assert_eq!(rules, LtlRules { birth: (2, 4), survival: (1, 8) });
+        assert!(rules.allows_birth(3) && !rules.allows_birth(5));
+        assert_eq!("b3/s2-3".parse::<LtlRules>().unwrap().birth, (3, 3));
+        assert!("B4-2/S1-8".parse::<LtlRules>().is_err());
+        assert!("S1-8".parse::<LtlRules>().is_err());
+    }
+
+    #[test]
+    fn test_phase_order_extremes() {
+        assert!((phase_order([1.0, 1.0, 1.0]) - 1.0).abs() < 1e-9);
+        assert!(phase_order([0.0, std::f64::consts::PI]) < 1e-9);
+        assert_eq!(phase_order([]), 0.0);
+    }
+}
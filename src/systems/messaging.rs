@@ -0,0 +1,196 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::constants::MESSAGE_INBOX_CAPACITY;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageKind {
+    Thought,
+    Request,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Recipients {
+    Cell(Uuid),
+    Neighbors,
+    Broadcast,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub from: Uuid,
+    pub kind: MessageKind,
+    // Set when the message carries one of the sender's stored thoughts
+    pub thought_id: Option<String>,
+    pub content: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl Message {
+    /// How the message reads in a recipient's context. Shared thoughts carry
+    /// the `cell/thought` reference the model can cite back.
+    pub fn summary(&self) -> String {
+        let content: String = self.content.chars().take(300).collect();
+        match (&self.kind, &self.thought_id) {
+            (MessageKind::Thought, Some(thought_id)) => format!("THOUGHT {}/{}: {}", self.from, thought_id, content),
+            (MessageKind::Thought, None) => format!("THOUGHT from {}: {}", self.from, content),
+            (MessageKind::Request, _) => format!("REQUEST from {}: {}", self.from, content),
+        }
+    }
+}
+
+/// Per-cell inboxes. Each holds at most `MESSAGE_INBOX_CAPACITY` messages and
+/// drops the oldest first. An inbox is emptied at most once per cycle.
+#[derive(Default)]
+pub struct MessageBus {
+    inboxes: HashMap<Uuid, VecDeque<Message>>,
+    taken_in: HashMap<Uuid, u64>, // Cycle each inbox was last emptied in
+    delivered: u64,
+}
+
+impl MessageBus {
+    pub fn deliver(&mut self, to: Uuid, message: Message) {
+        let inbox = self.inboxes.entry(to).or_default();
+        inbox.push_back(message);
+        if inbox.len() > MESSAGE_INBOX_CAPACITY {
+            inbox.pop_front();
+        }
+        self.delivered += 1;
+    }
+
+    /// Empties and returns `cell_id`'s inbox, oldest message first. Returns
+    /// nothing if the inbox was already emptied in `cycle`.
+    pub fn take(&mut self, cell_id: Uuid, cycle: u64) -> Vec<Message> {
+        if self.taken_in.insert(cell_id, cycle) == Some(cycle) {
+            return Vec::new();
+        }
+        self.inboxes.remove(&cell_id).map(Vec::from).unwrap_or_default()
+    }
+
+    /// Puts back messages a cell couldn't act on, ahead of anything that
+    /// arrived since, so the cell can read them again this cycle.
+    pub fn requeue(&mut self, cell_id: Uuid, messages: Vec<Message>) {
+        self.taken_in.remove(&cell_id);
+        if messages.is_empty() {
+            return;
+        }
+        let inbox = self.inboxes.entry(cell_id).or_default();
+        for message in messages.into_iter().rev() {
+            inbox.push_front(message);
+        }
+        while inbox.len() > MESSAGE_INBOX_CAPACITY {
+            inbox.pop_front();
+        }
+    }
+
+    pub fn drop_inbox(&mut self, cell_id: Uuid) {
+        self.inboxes.remove(&cell_id);
+        self.taken_in.remove(&cell_id);
+    }
+
+    pub fn pending(&self) -> usize {
+        self.inboxes.values().map(VecDeque::len).sum()
+    }
+
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+}
+
+/// A real exchange with another cell, as recorded in its collaboration history.
+#[derive(Clone, Debug)]
+pub struct Collaboration {
+    pub peer: Uuid,
+    pub note: String,
+    pub at: DateTime<Utc>,
+}
+
+/// `cell/thought` pairs from a thought's `REFERENCES:` line.
+pub fn parse_references(content: &str) -> Vec<(Uuid, String)> {
+    content.lines()
+        .filter_map(|line| line.trim().strip_prefix("REFERENCES:"))
+        .flat_map(|refs| refs.split(','))
+        .filter_map(|reference| {
+            let (cell_id, thought_id) = reference.trim().split_once('/')?;
+            let cell_id = Uuid::parse_str(cell_id.trim()).ok()?;
+            Some((cell_id, thought_id.trim().to_string()))
+        })
+        .filter(|(_, thought_id)| !thought_id.is_empty())
+        .collect()
+}
+
+/// Questions a thought addresses to the cell's neighbours on `REQUEST:` lines.
+pub fn parse_requests(content: &str) -> Vec<String> {
+    content.lines()
+        .filter_map(|line| line.trim().strip_prefix("REQUEST:"))
+        .map(|request| request.trim().to_string())
+        .filter(|request| !request.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_references_and_requests() {
+        let cell = Uuid::new_v4();
+        let content = format!("Some insight\nREFERENCES: {}/abc-1, not-a-uuid/x, {}/\nREQUEST: who has data on soil?\nREQUEST:", cell, cell);
+        assert_eq!(parse_references(&content), vec![(cell, "abc-1".to_string())]);
+        assert_eq!(parse_requests(&content), vec!["who has data on soil?".to_string()]);
+    }
+
+    fn request(content: &str) -> Message {
+        Message {
+            from: Uuid::new_v4(),
+            kind: MessageKind::Request,
+            thought_id: None,
+            content: content.to_string(),
+            sent_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_inbox_drops_oldest_past_capacity() {
+        let mut bus = MessageBus::default();
+        let to = Uuid::new_v4();
+        for i in 0..MESSAGE_INBOX_CAPACITY + 2 {
+            bus.deliver(to, request(&i.to_string()));
+        }
+        let inbox = bus.take(to, 0);
+        assert_eq!(inbox.len(), MESSAGE_INBOX_CAPACITY);
+        assert_eq!(inbox[0].content, "2");
+        assert_eq!(bus.pending(), 0);
+    }
+
+    #[test]
+    fn test_inbox_is_taken_once_per_cycle_and_requeued() {
+        let mut bus = MessageBus::default();
+        let to = Uuid::new_v4();
+        bus.deliver(to, request("first"));
+        let inbox = bus.take(to, 1);
+        assert_eq!(inbox.len(), 1);
+
+        bus.deliver(to, request("second"));
+        assert!(bus.take(to, 1).is_empty());
+
+        // A failed thought hands its messages back ahead of newer ones
+        bus.requeue(to, inbox);
+        let contents: Vec<String> = bus.take(to, 1).into_iter().map(|m| m.content).collect();
+        assert_eq!(contents, vec!["first", "second"]);
+        bus.deliver(to, request("third"));
+        assert_eq!(bus.take(to, 2).len(), 1);
+    }
+}
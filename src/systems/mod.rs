@@ -14,5 +14,12 @@ pub mod ltl;
 pub mod quantum;
 pub mod ndarray_serde;
 pub mod lenia;
+pub mod novelty;
+pub mod lineage;
+pub mod spatial;
+pub mod movement;
+pub mod messaging;
+pub mod execution;
+pub mod consensus;
 
 pub use lenia::{LeniaWorld, LeniaParams};
@@ -0,0 +1,158 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::constants::{
+    MOVEMENT_COMFORT_NEIGHBORS, MOVEMENT_COMPLEMENT_WEIGHT, MOVEMENT_CROWDING_WEIGHT, MOVEMENT_ENERGY_COST,
+    MOVEMENT_ENERGY_WEIGHT, MOVEMENT_FIELD_WEIGHT, MOVEMENT_MAX_SPEED, MOVEMENT_SENSE_RADIUS,
+    MOVEMENT_WORLD_MAX, MOVEMENT_WORLD_MIN,
+};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Weights of the forces that move cells through space each cycle. Missing
+/// fields in a JSON rules file keep their defaults; a `max_speed` of zero
+/// turns movement off. Cells stay inside the `world_min..world_max` cube on
+/// every axis.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MovementRules {
+    pub complement_weight: f64,
+    pub field_weight: f64,
+    pub energy_weight: f64,
+    pub crowding_weight: f64,
+    pub comfort_neighbors: usize,
+    pub sense_radius: f64,
+    pub max_speed: f64,
+    pub energy_cost: f64, // Per world unit travelled
+    pub world_min: f64,
+    pub world_max: f64,
+}
+
+impl Default for MovementRules {
+    fn default() -> Self {
+        Self {
+            complement_weight: MOVEMENT_COMPLEMENT_WEIGHT,
+            field_weight: MOVEMENT_FIELD_WEIGHT,
+            energy_weight: MOVEMENT_ENERGY_WEIGHT,
+            crowding_weight: MOVEMENT_CROWDING_WEIGHT,
+            comfort_neighbors: MOVEMENT_COMFORT_NEIGHBORS,
+            sense_radius: MOVEMENT_SENSE_RADIUS,
+            max_speed: MOVEMENT_MAX_SPEED,
+            energy_cost: MOVEMENT_ENERGY_COST,
+            world_min: MOVEMENT_WORLD_MIN,
+            world_max: MOVEMENT_WORLD_MAX,
+        }
+    }
+}
+
+/// What a cell perceives of one nearby cell.
+pub struct SensedNeighbor {
+    pub offset: [f64; 3],
+    pub distance: f64,
+    pub complement: f64,
+    pub energy: f64,
+}
+
+impl MovementRules {
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_speed > 0.0
+    }
+
+    /// One cycle's displacement: pulled toward complementary and energetic
+    /// neighbours and up the Lenia activity gradient, pushed apart once more
+    /// than `comfort_neighbors` cells are in range, and capped at `max_speed`.
+    /// Steps that would leave the world reflect off its walls; a cell that
+    /// already lies outside may move inward but never further out.
+    pub fn velocity(&self, position: [f64; 3], neighbors: &[SensedNeighbor], field_gradient: [f64; 3]) -> [f64; 3] {
+        let mut velocity = field_gradient.map(|g| g * self.field_weight);
+        let sensed: Vec<&SensedNeighbor> = neighbors.iter()
+            .filter(|n| n.distance > 0.0 && n.distance <= self.sense_radius)
+            .collect();
+
+        if !sensed.is_empty() {
+            let crowding = sensed.len().saturating_sub(self.comfort_neighbors) as f64 / sensed.len() as f64;
+            for neighbor in &sensed {
+                let closeness = 1.0 - neighbor.distance / self.sense_radius;
+                let pull = self.complement_weight * neighbor.complement
+                    + self.energy_weight * neighbor.energy / 100.0
+                    - self.crowding_weight * crowding * closeness;
+                for (axis, v) in velocity.iter_mut().enumerate() {
+                    *v += pull * neighbor.offset[axis] / neighbor.distance / sensed.len() as f64;
+                }
+            }
+        }
+
+        let speed = velocity.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if speed > self.max_speed {
+            velocity = velocity.map(|v| v * self.max_speed / speed);
+        }
+
+        for (axis, v) in velocity.iter_mut().enumerate() {
+            let (p, lo, hi) = (position[axis], self.world_min.min(position[axis]), self.world_max.max(position[axis]));
+            let mut target = p + *v;
+            if target > hi {
+                target = 2.0 * hi - target;
+            } else if target < lo {
+                target = 2.0 * lo - target;
+            }
+            *v = target.clamp(lo, hi) - p;
+        }
+        velocity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbor(x: f64, complement: f64) -> SensedNeighbor {
+        SensedNeighbor { offset: [x, 0.0, 0.0], distance: x.abs(), complement, energy: 0.0 }
+    }
+
+    #[test]
+    fn test_drifts_toward_complement_and_respects_speed() {
+        let rules = MovementRules::default();
+        let velocity = rules.velocity([0.0; 3], &[neighbor(2.0, 1.0), neighbor(-2.0, 0.1)], [0.0; 3]);
+        assert!(velocity[0] > 0.0);
+
+        let velocity = rules.velocity([0.0; 3], &[], [100.0, 0.0, 0.0]);
+        assert!((velocity[0] - rules.max_speed).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_steps_reflect_off_the_world_bounds() {
+        let rules = MovementRules { world_min: -1.0, world_max: 1.0, ..MovementRules::default() };
+        let velocity = rules.velocity([0.8, 0.0, 0.0], &[], [100.0, 0.0, 0.0]);
+        assert!((0.8 + velocity[0] - 0.7).abs() < 1e-9);
+        let velocity = rules.velocity([0.0, 0.0, -0.9], &[], [0.0, 0.0, -100.0]);
+        assert!((-0.9 + velocity[2] + 0.6).abs() < 1e-9);
+
+        // Outside the world a cell is free to head back but not further out
+        let velocity = rules.velocity([5.0, 0.0, 0.0], &[], [100.0, 0.0, 0.0]);
+        assert!(velocity[0] <= 0.0);
+        let velocity = rules.velocity([5.0, 0.0, 0.0], &[], [-100.0, 0.0, 0.0]);
+        assert!(velocity[0] < 0.0);
+    }
+
+    #[test]
+    fn test_crowded_cells_spread_out() {
+        let rules = MovementRules { complement_weight: 0.0, ..MovementRules::default() };
+        let mut crowd: Vec<SensedNeighbor> = (0..rules.comfort_neighbors * 2).map(|_| neighbor(0.5, 0.0)).collect();
+        crowd.push(neighbor(-3.5, 0.0));
+        assert!(rules.velocity([0.0; 3], &crowd, [0.0; 3])[0] < 0.0);
+    }
+}
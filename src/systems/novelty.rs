@@ -0,0 +1,184 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+//! Novelty of a thought relative to what the cell and colony already thought.
+//! Thoughts are compared by embedding when both have one, otherwise by word
+//! shingles.
+
+use crate::api::embeddings::cosine_similarity;
+use crate::models::types::Thought;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+const SHINGLE_SIZE: usize = 3;
+
+/// Where the closest earlier thought was found.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClosestThought {
+    None,
+    Own { index: usize, similarity: f64 },
+    Colony { similarity: f64 },
+}
+
+impl ClosestThought {
+    pub fn similarity(&self) -> f64 {
+        match self {
+            ClosestThought::None => 0.0,
+            ClosestThought::Own { similarity, .. } | ClosestThought::Colony { similarity } => *similarity,
+        }
+    }
+
+    /// Novelty is one minus the similarity to the closest earlier thought.
+    pub fn novelty(&self) -> f64 {
+        1.0 - self.similarity()
+    }
+}
+
+pub fn shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+
+    if words.len() < SHINGLE_SIZE {
+        return words.iter().map(|w| hash_words(std::slice::from_ref(w))).collect();
+    }
+
+    words.windows(SHINGLE_SIZE).map(hash_words).collect()
+}
+
+fn hash_words(words: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    words.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    intersection as f64 / (a.len() + b.len() - intersection) as f64
+}
+
+/// Similarity in [0, 1] between a candidate (with precomputed shingles) and an earlier thought.
+fn similarity(thought: &Thought, thought_shingles: &HashSet<u64>, other: &Thought) -> f64 {
+    match (&thought.embedding, &other.embedding) {
+        (Some(a), Some(b)) if a.len() == b.len() => (cosine_similarity(a, b) as f64).clamp(0.0, 1.0),
+        _ => jaccard(thought_shingles, &shingles(&other.content)),
+    }
+}
+
+/// Finds the earlier thought most similar to `thought`, preferring the cell's own
+/// history on ties so duplicates of its own ideas can be merged in place.
+pub fn closest_thought<'a>(
+    thought: &Thought,
+    own_history: impl Iterator<Item = &'a Thought>,
+    colony_recent: impl Iterator<Item = &'a Thought>,
+) -> ClosestThought {
+    let thought_shingles = shingles(&thought.content);
+    let mut closest = ClosestThought::None;
+
+    for (index, other) in own_history.enumerate() {
+        let similarity = similarity(thought, &thought_shingles, other);
+        if similarity > closest.similarity() {
+            closest = ClosestThought::Own { index, similarity };
+        }
+    }
+
+    for other in colony_recent {
+        let similarity = similarity(thought, &thought_shingles, other);
+        if similarity > closest.similarity() {
+            closest = ClosestThought::Colony { similarity };
+        }
+    }
+
+    closest
+}
+
+/// Folds a duplicate into the thought it repeats, keeping the stronger scores
+/// and any factors or tags the earlier thought lacked.
+pub fn merge_duplicate(existing: &mut Thought, duplicate: Thought) {
+    existing.relevance_score = existing.relevance_score.max(duplicate.relevance_score);
+    existing.confidence_score = existing.confidence_score.max(duplicate.confidence_score);
+    existing.timestamp = existing.timestamp.max(duplicate.timestamp);
+
+    for factor in duplicate.real_time_factors {
+        if !existing.real_time_factors.contains(&factor) {
+            existing.real_time_factors.push(factor);
+        }
+    }
+    for tag in duplicate.context_tags {
+        if !existing.context_tags.contains(&tag) {
+            existing.context_tags.push(tag);
+        }
+    }
+    for reference in duplicate.referenced_thoughts {
+        if !existing.referenced_thoughts.contains(&reference) {
+            existing.referenced_thoughts.push(reference);
+        }
+    }
+    if existing.embedding.is_none() {
+        existing.embedding = duplicate.embedding;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn thought(content: &str) -> Thought {
+        Thought {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: content.to_string(),
+            timestamp: Utc::now(),
+            relevance_score: 0.5,
+            context_tags: Vec::new(),
+            real_time_factors: Vec::new(),
+            confidence_score: 0.5,
+            ascii_visualization: None,
+            referenced_thoughts: Vec::new(),
+            embedding: None,
+            novelty_score: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_thoughts_saved_without_novelty_load_as_novel() {
+        let mut saved = serde_json::to_value(thought("An older thought")).unwrap();
+        saved.as_object_mut().unwrap().remove("novelty_score");
+        let loaded: Thought = serde_json::from_value(saved).unwrap();
+        assert_eq!(loaded.novelty_score, 1.0);
+    }
+
+    #[test]
+    fn test_closest_thought_prefers_own_history() {
+        let candidate = thought("Distributed consensus emerges from local feedback loops between cells");
+        let own = [thought("Energy budgets limit growth"), thought(&candidate.content.to_uppercase())];
+        let colony = [thought(&candidate.content)];
+
+        let closest = closest_thought(&candidate, own.iter(), colony.iter());
+        assert_eq!(closest, ClosestThought::Own { index: 1, similarity: 1.0 });
+        assert_eq!(closest.novelty(), 0.0);
+    }
+
+    #[test]
+    fn test_unrelated_thought_is_novel() {
+        let candidate = thought("Quantum annealing schedules for sparse graphs");
+        let colony = [thought("Market sentiment shifted toward open source tooling")];
+
+        let closest = closest_thought(&candidate, std::iter::empty(), colony.iter());
+        assert_eq!(closest.novelty(), 1.0);
+    }
+}
@@ -0,0 +1,225 @@
+// MIT License
+
+/*Copyright (c) 2024 Based Labs
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the "Software"), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.*/
+
+
+use crate::models::types::Coordinates;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+type BucketKey = (i64, i64, i64);
+
+/// Uniform grid over cell positions. Radius queries only visit the buckets
+/// that overlap the search sphere instead of scanning the whole colony.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex {
+    bucket_size: f64,
+    buckets: HashMap<BucketKey, Vec<Uuid>>,
+    points: HashMap<Uuid, [f64; 3]>,
+}
+
+impl SpatialIndex {
+    pub fn new(bucket_size: f64) -> Self {
+        Self {
+            bucket_size: bucket_size.max(f64::EPSILON),
+            buckets: HashMap::new(),
+            points: HashMap::new(),
+        }
+    }
+
+    fn key(&self, point: [f64; 3]) -> BucketKey {
+        (
+            (point[0] / self.bucket_size).floor() as i64,
+            (point[1] / self.bucket_size).floor() as i64,
+            (point[2] / self.bucket_size).floor() as i64,
+        )
+    }
+
+    /// Adds `id` at `position`, or moves it there if it is already indexed.
+    pub fn insert(&mut self, id: Uuid, position: &Coordinates) {
+        let point = [position.x, position.y, position.z];
+        if let Some(old) = self.points.insert(id, point) {
+            let old_key = self.key(old);
+            if old_key == self.key(point) {
+                return;
+            }
+            self.detach(id, old_key);
+        }
+        let key = self.key(point);
+        self.buckets.entry(key).or_default().push(id);
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        match self.points.remove(&id) {
+            Some(point) => {
+                let key = self.key(point);
+                self.detach(id, key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn detach(&mut self, id: Uuid, key: BucketKey) {
+        if let Some(bucket) = self.buckets.get_mut(&key) {
+            bucket.retain(|other| *other != id);
+            if bucket.is_empty() {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.points.clear();
+    }
+
+    /// Indexed cells within `radius` of `position` (inclusive), nearest first.
+    /// A cell sitting exactly at `position` is included at distance zero.
+    pub fn within(&self, position: &Coordinates, radius: f64) -> Vec<(Uuid, f64)> {
+        let center = [position.x, position.y, position.z];
+        self.within_point(center, radius)
+    }
+
+    fn within_point(&self, center: [f64; 3], radius: f64) -> Vec<(Uuid, f64)> {
+        let reach = (radius / self.bucket_size).ceil() as i64;
+        let (cx, cy, cz) = self.key(center);
+        let mut found = Vec::new();
+
+        for x in cx - reach..=cx + reach {
+            for y in cy - reach..=cy + reach {
+                for z in cz - reach..=cz + reach {
+                    let Some(bucket) = self.buckets.get(&(x, y, z)) else { continue };
+                    for id in bucket {
+                        let point = self.points[id];
+                        let distance = ((point[0] - center[0]).powi(2)
+                            + (point[1] - center[1]).powi(2)
+                            + (point[2] - center[2]).powi(2))
+                            .sqrt();
+                        if distance <= radius {
+                            found.push((*id, distance));
+                        }
+                    }
+                }
+            }
+        }
+
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+    }
+
+    /// Groups of cells connected by hops shorter than `radius`.
+    pub fn clusters(&self, radius: f64) -> Vec<Vec<Uuid>> {
+        let ids: Vec<Uuid> = self.points.keys().copied().collect();
+        let slots: HashMap<Uuid, usize> = ids.iter().enumerate().map(|(slot, id)| (*id, slot)).collect();
+        let mut sets = UnionFind::new(ids.len());
+
+        for (slot, id) in ids.iter().enumerate() {
+            for (other, distance) in self.within_point(self.points[id], radius) {
+                if distance < radius {
+                    sets.union(slot, slots[&other]);
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<Uuid>> = HashMap::new();
+        for (slot, id) in ids.iter().enumerate() {
+            groups.entry(sets.find(slot)).or_default().push(*id);
+        }
+        groups.into_values().collect()
+    }
+}
+
+/// Disjoint sets with union by size and iterative path halving, so deep
+/// chains never recurse.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub fn find(&mut self, mut item: usize) -> usize {
+        while self.parent[item] != item {
+            self.parent[item] = self.parent[self.parent[item]];
+            item = self.parent[item];
+        }
+        item
+    }
+
+    /// Merges the sets holding `a` and `b`; false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: f64, y: f64, z: f64) -> Coordinates {
+        Coordinates { x, y, z, ..Default::default() }
+    }
+
+    #[test]
+    fn test_radius_query_matches_brute_force_after_moves() {
+        let mut index = SpatialIndex::new(2.0);
+        let ids: Vec<Uuid> = (0..200).map(|_| Uuid::new_v4()).collect();
+        let mut positions = HashMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            let position = at((i % 10) as f64 * 0.9 - 4.0, (i / 10) as f64 * 0.7 - 7.0, (i % 7) as f64 - 3.0);
+            index.insert(*id, &position);
+            positions.insert(*id, position);
+        }
+        for id in ids.iter().step_by(3) {
+            let moved = at(positions[id].x + 3.3, positions[id].y - 1.1, positions[id].z);
+            index.insert(*id, &moved);
+            positions.insert(*id, moved);
+        }
+        index.remove(ids[5]);
+        positions.remove(&ids[5]);
+
+        let center = at(0.5, -1.0, 0.0);
+        let mut expected: Vec<Uuid> = positions.iter()
+            .filter(|(_, p)| ((p.x - center.x).powi(2) + (p.y - center.y).powi(2) + (p.z - center.z).powi(2)).sqrt() <= 3.0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut found: Vec<Uuid> = index.within(&center, 3.0).into_iter().map(|(id, _)| id).collect();
+        expected.sort();
+        found.sort();
+        assert_eq!(found, expected);
+        assert!(!index.within(&positions[&ids[6]], 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_long_chain_is_one_cluster() {
+        let mut index = SpatialIndex::new(2.0);
+        for i in 0..20_000 {
+            index.insert(Uuid::new_v4(), &at(i as f64 * 1.5, 0.0, 0.0));
+        }
+        index.insert(Uuid::new_v4(), &at(0.0, 100.0, 0.0));
+        assert_eq!(index.clusters(2.0).len(), 2);
+    }
+}